@@ -55,7 +55,6 @@ fn main() {
         win_state,
         win_ids,
         array_wins: vec![],
-        reusable_win_ids: vec![],
         next_array_win_idx: 1,
         hide_test2: false,
     };
@@ -157,7 +156,6 @@ struct UiState {
     win_state: WindowingState,
     win_ids: WinIds,
     array_wins: Vec<ArrayWinState>,
-    reusable_win_ids: Vec<WinId>,
     next_array_win_idx: usize,
     hide_test2: bool,
 }
@@ -256,14 +254,16 @@ fn set_widgets(
     // at the end of the scope. Putting it in a function also works.
     std::mem::drop(win_ctx);
 
-    // Create new windows, getting new `WinId`s if necessary.
+    // Remove the windows to be closed. The `WindowingState` destroys them
+    // once we stop calling `make_window` for them and recycles their slots
+    // by itself, so we can simply forget about their `WinId`s.
+    for i in array_win_to_close.into_iter().rev() {
+        state.array_wins.swap_remove(i);
+    }
+
+    // Create new windows with new `WinId`s.
     while add_win > 0 {
-        let win_state = &mut state.win_state;
-        // Reuse an existing `WinId` if available or get a new `WinId`.
-        let win_id = state
-            .reusable_win_ids
-            .pop()
-            .unwrap_or_else(|| win_state.next_id());
+        let win_id = state.win_state.next_id();
         state.array_wins.push(ArrayWinState {
             index: state.next_array_win_idx,
             win_id,
@@ -271,14 +271,4 @@ fn set_widgets(
         state.next_array_win_idx += 1;
         add_win -= 1;
     }
-
-    // Remove the windows to be closed. Note that we do this *after* creating
-    // new windows, because the windows are only destroyed after the next
-    // iteration and we don't want new windows to re-use the leftover states of
-    // these windows which are yet to be destroyed.
-    for i in array_win_to_close.into_iter().rev() {
-        let s = state.array_wins.swap_remove(i);
-        // We want to be able to reuse the `WinId`.
-        state.reusable_win_ids.push(s.win_id);
-    }
 }
@@ -164,24 +164,35 @@ fn make_frame(
     iter_chain![lower_a, upper_a, lower_b, upper_b, inside].map(widget::triangles::Triangle)
 }
 
+/// Mixes two colors half and half.
+fn mix(a: color::Rgba, b: color::Rgba) -> color::Rgba {
+    color::Rgba(
+        (a.0 + b.0) / 2.0,
+        (a.1 + b.1) / 2.0,
+        (a.2 + b.2) / 2.0,
+        (a.3 + b.3) / 2.0,
+    )
+}
+
 pub(super) fn make_panel_frame(
     bottom_left: [f64; 2],
     top_right: [f64; 2],
     border_thickness: f64,
     base_color: color::Color,
+    highlight_color: color::Color,
+    shadow_color: color::Color,
 ) -> impl Iterator<Item = widget::triangles::Triangle<widget::triangles::ColoredPoint>> {
-    let hsla = base_color.to_hsl();
-    let alpha = hsla.3;
+    let alpha = base_color.to_rgb().3;
     // The original colors are greyscale with luminance of:
     //     0.0, 0.875, 0.5, 1.0, 0.75
-    // We treat the base colour as the fifth colour and scale the other colours
-    // based on the original scales --
-    //     0.875 = (1.0 - 0.75) / 2.0 + 0.75
-    //     0.5 = 0.75 / 1.5
+    // We treat the base colour as the fifth colour, the highlight colour as
+    // the fourth and the shadow colour as the third. The second colour is
+    // between the base and the highlight colours --
+    //     0.875 = (1.0 + 0.75) / 2.0
     let lower_a = color::Rgba(0.0, 0.0, 0.0, alpha);
-    let upper_a = color::hsla(hsla.0, hsla.1, (1.0 - hsla.2) / 2.0 + hsla.2, alpha).to_rgb();
-    let lower_b = color::hsla(hsla.0, hsla.1, hsla.2 / 1.5, alpha).to_rgb();
-    let upper_b = color::Rgba(1.0, 1.0, 1.0, alpha);
+    let upper_a = mix(base_color.to_rgb(), highlight_color.to_rgb());
+    let lower_b = shadow_color.to_rgb();
+    let upper_b = highlight_color.to_rgb();
     let inside = base_color.to_rgb();
     let frame_color = FrameColor {
         lower_a,
@@ -198,27 +209,28 @@ pub(super) fn make_button_frame(
     bottom_left: [f64; 2],
     top_right: [f64; 2],
     border_thickness: f64,
-    base_color: color::Color,
+    face_color: color::Color,
+    highlight_color: color::Color,
+    shadow_color: color::Color,
     is_clicked: bool,
 ) -> impl Iterator<Item = widget::triangles::Triangle<widget::triangles::ColoredPoint>> {
-    let hsla = base_color.to_hsl();
-    let alpha = hsla.3;
+    let alpha = face_color.to_rgb().3;
     // The original colors are greyscale with luminance of:
     //     0.0, 1.0, 0.5, 0.875, 0.75
-    // We treat the base colour as the fifth colour and scale the other colours
-    // based on the original scales --
-    //     0.875 = (1.0 - 0.75) / 2.0 + 0.75
-    //     0.5 = 0.75 / 1.5
+    // We treat the face colour as the fifth colour, the highlight colour as
+    // the second and the shadow colour as the third. The fourth colour is
+    // between the face and the highlight colours --
+    //     0.875 = (1.0 + 0.75) / 2.0
     let lower_a = color::Rgba(0.0, 0.0, 0.0, alpha);
-    let upper_a = color::Rgba(1.0, 1.0, 1.0, alpha);
-    let lower_b = color::hsla(hsla.0, hsla.1, hsla.2 / 1.5, alpha).to_rgb();
-    let upper_b = color::hsla(hsla.0, hsla.1, (1.0 - hsla.2) / 2.0 + hsla.2, alpha).to_rgb();
+    let upper_a = highlight_color.to_rgb();
+    let lower_b = shadow_color.to_rgb();
+    let upper_b = mix(face_color.to_rgb(), highlight_color.to_rgb());
     let (lower_a, upper_a, lower_b, upper_b) = if is_clicked {
         (upper_a, lower_a, upper_b, lower_b)
     } else {
         (lower_a, upper_a, lower_b, upper_b)
     };
-    let inside = base_color.to_rgb();
+    let inside = face_color.to_rgb();
     let frame_color = FrameColor {
         lower_a,
         upper_a,
@@ -462,3 +474,140 @@ fn make_down_arrow_icon_shape(
     ];
     std::iter::once(triangle).map(widget::triangles::Triangle)
 }
+
+pub(super) fn make_maximize_button_icon(
+    bottom_left: [f64; 2],
+    top_right: [f64; 2],
+    hidpi_factor: f64,
+) -> impl Iterator<Item = widget::triangles::Triangle<conrod_core::Point>> {
+    let (icon_bottom_left, icon_top_right, unit) =
+        window_icon_rect(bottom_left, top_right, hidpi_factor);
+    make_window_icon_shape(icon_bottom_left, icon_top_right, unit)
+}
+
+pub(super) fn make_restore_button_icon(
+    bottom_left: [f64; 2],
+    top_right: [f64; 2],
+    hidpi_factor: f64,
+) -> impl Iterator<Item = widget::triangles::Triangle<conrod_core::Point>> {
+    let (icon_bottom_left, icon_top_right, unit) =
+        window_icon_rect(bottom_left, top_right, hidpi_factor);
+    make_restore_icon_shape(icon_bottom_left, icon_top_right, unit)
+}
+
+/// Calculates the rect of a square window-shaped icon in the button, returning
+/// the bottom-left and top-right corners and the line thickness.
+fn window_icon_rect(
+    bottom_left: [f64; 2],
+    top_right: [f64; 2],
+    hidpi_factor: f64,
+) -> ([f64; 2], [f64; 2], f64) {
+    let [x_o, y_o] = bottom_left;
+    let [x_e, y_e] = top_right;
+    let width = x_e - x_o;
+    let height = y_e - y_o;
+    let px_width = (width * hidpi_factor).round();
+    let px_height = (height * hidpi_factor).round();
+    let icon_px_size = {
+        let shape_size_from_width = (px_width / 11.0 * 9.0).round();
+        let shape_size_from_height = (px_height / 9.0 * 9.0).round();
+        shape_size_from_width.min(shape_size_from_height)
+    };
+    let icon_pad_left = ((px_width - icon_px_size) / 2.0).round() / hidpi_factor;
+    let icon_pad_bottom = ((px_height - icon_px_size) / 2.0).round() / hidpi_factor;
+    let icon_size = icon_px_size / hidpi_factor;
+    let unit = (icon_px_size / 9.0).round().max(1.0) / hidpi_factor;
+
+    let icon_bottom_left = [x_o + icon_pad_left, y_o + icon_pad_bottom];
+    let icon_top_right = [
+        x_o + icon_pad_left + icon_size,
+        y_o + icon_pad_bottom + icon_size,
+    ];
+    (icon_bottom_left, icon_top_right, unit)
+}
+
+fn make_window_outline(
+    bottom_left: [f64; 2],
+    top_right: [f64; 2],
+    line_thickness: f64,
+) -> impl Iterator<Item = [conrod_core::Point; 3]> {
+    // The outline of a window with a thicker title bar, laid out as follow:
+    //
+    //      ________
+    //     |________|
+    //     | |    | |
+    //     |_|____|_|
+    //     |________|
+    let [x_o, y_o] = bottom_left;
+    let [x_e, y_e] = top_right;
+    let title_bar = make_rect([x_o, y_e], [x_e, y_e - line_thickness * 2.0]);
+    let left = make_rect(
+        [x_o, y_e - line_thickness * 2.0],
+        [x_o + line_thickness, y_o + line_thickness],
+    );
+    let right = make_rect(
+        [x_e - line_thickness, y_e - line_thickness * 2.0],
+        [x_e, y_o + line_thickness],
+    );
+    let bottom = make_rect([x_o, y_o + line_thickness], [x_e, y_o]);
+    iter_chain![
+        polygon_to_triangle_points(title_bar),
+        polygon_to_triangle_points(left),
+        polygon_to_triangle_points(right),
+        polygon_to_triangle_points(bottom),
+    ]
+}
+
+fn make_window_icon_shape(
+    bottom_left: [f64; 2],
+    top_right: [f64; 2],
+    line_thickness: f64,
+) -> impl Iterator<Item = widget::triangles::Triangle<conrod_core::Point>> {
+    make_window_outline(bottom_left, top_right, line_thickness).map(widget::triangles::Triangle)
+}
+
+fn make_restore_icon_shape(
+    bottom_left: [f64; 2],
+    top_right: [f64; 2],
+    line_thickness: f64,
+) -> impl Iterator<Item = widget::triangles::Triangle<conrod_core::Point>> {
+    // Two overlapping windows, with the back window drawn only where it is
+    // not covered by the front window:
+    //
+    //         ______
+    //        |______|
+    //      __|___   |
+    //     |______|__|
+    //     | |  | |
+    //     |_|__|_|
+    //     |______|
+    let [x_o, y_o] = bottom_left;
+    let [x_e, y_e] = top_right;
+    let offset_x = ((x_e - x_o) / 3.0 / line_thickness).round() * line_thickness;
+    let offset_y = ((y_e - y_o) / 3.0 / line_thickness).round() * line_thickness;
+
+    // The front window at the bottom-left:
+    let front = make_window_outline([x_o, y_o], [x_e - offset_x, y_e - offset_y], line_thickness);
+
+    // The visible parts of the back window at the top-right:
+    let back_x_o = x_o + offset_x;
+    let back_y_o = y_o + offset_y;
+    let back_title_bar = make_rect([back_x_o, y_e], [x_e, y_e - line_thickness * 2.0]);
+    let back_left = make_rect(
+        [back_x_o, y_e - line_thickness * 2.0],
+        [back_x_o + line_thickness, y_e - offset_y],
+    );
+    let back_right = make_rect(
+        [x_e - line_thickness, y_e - line_thickness * 2.0],
+        [x_e, back_y_o + line_thickness],
+    );
+    let back_bottom = make_rect([x_e - offset_x, back_y_o + line_thickness], [x_e, back_y_o]);
+    iter_chain![
+        front,
+        polygon_to_triangle_points(back_title_bar),
+        polygon_to_triangle_points(back_left),
+        polygon_to_triangle_points(back_right),
+        polygon_to_triangle_points(back_bottom),
+    ]
+    .map(widget::triangles::Triangle)
+}
@@ -0,0 +1,220 @@
+use crate::windowing_area::WindowFrameStyle;
+
+use conrod_core::{color, Color, Theme};
+
+/// The colors of the window decorations, which the `FrameTheme` draws with.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct ColorScheme {
+    /// The colors of the title bar of the focused window, from left to right.
+    /// Themes with solid title bars use the right color.
+    pub active_title: [Color; 2],
+    /// The colors of the title bar of the other windows, from left to right.
+    pub inactive_title: [Color; 2],
+    pub active_title_text: Color,
+    pub inactive_title_text: Color,
+    /// The base color of the window frame and the tab strip.
+    pub frame_base: Color,
+    /// The color of text drawn on the frame, such as the tab titles.
+    pub frame_text: Color,
+    pub button_face: Color,
+    /// The color of the lit edges of bevelled frames and buttons.
+    pub button_highlight: Color,
+    /// The color of the shaded edges of bevelled frames and buttons.
+    pub button_shadow: Color,
+    /// The color of the icons on the title bar buttons.
+    pub icon: Color,
+}
+
+impl ColorScheme {
+    /// The light scheme of the classic look, which is the default.
+    pub fn light() -> Self {
+        Self {
+            active_title: [
+                color::rgba(0.0, 0.0, 0.5, 1.0),
+                color::rgba(0.05, 0.5, 0.8, 1.0),
+            ],
+            inactive_title: [
+                color::rgba(0.5, 0.5, 0.5, 1.0),
+                color::rgba(0.7, 0.7, 0.7, 1.0),
+            ],
+            active_title_text: color::WHITE,
+            inactive_title_text: color::WHITE,
+            frame_base: color::rgba(0.75, 0.75, 0.75, 1.0),
+            frame_text: color::BLACK,
+            button_face: color::rgba(0.75, 0.75, 0.75, 1.0),
+            button_highlight: color::WHITE,
+            button_shadow: color::rgba(0.5, 0.5, 0.5, 1.0),
+            icon: color::BLACK,
+        }
+    }
+
+    /// A dark scheme with muted title bars and light text.
+    pub fn dark() -> Self {
+        Self {
+            active_title: [
+                color::rgba(0.1, 0.2, 0.45, 1.0),
+                color::rgba(0.15, 0.35, 0.6, 1.0),
+            ],
+            inactive_title: [
+                color::rgba(0.25, 0.25, 0.25, 1.0),
+                color::rgba(0.32, 0.32, 0.32, 1.0),
+            ],
+            active_title_text: color::rgba(0.95, 0.95, 0.95, 1.0),
+            inactive_title_text: color::rgba(0.7, 0.7, 0.7, 1.0),
+            frame_base: color::rgba(0.22, 0.22, 0.22, 1.0),
+            frame_text: color::rgba(0.9, 0.9, 0.9, 1.0),
+            button_face: color::rgba(0.3, 0.3, 0.3, 1.0),
+            button_highlight: color::rgba(0.45, 0.45, 0.45, 1.0),
+            button_shadow: color::rgba(0.12, 0.12, 0.12, 1.0),
+            icon: color::rgba(0.9, 0.9, 0.9, 1.0),
+        }
+    }
+
+    /// Makes a scheme from the conrod `Theme`, which is used when no scheme
+    /// is set on the `WindowingArea`. The colors are taken from the
+    /// `widget_styling` entry for `WindowFrameStyle` if the theme has one,
+    /// otherwise from the background, shape and label colors of the theme.
+    /// The title bars and the buttons are drawn with the shape color.
+    pub fn from_theme(theme: &Theme) -> Self {
+        let defaults = WindowFrameStyle::default();
+        let style = WindowFrameStyle {
+            frame_color: Some(defaults.frame_color(theme)),
+            title_bar_color: Some(defaults.title_bar_color(theme)),
+            title_bar_text_color: Some(defaults.title_bar_text_color(theme)),
+            ..defaults
+        };
+        let mut colors = Self {
+            frame_text: theme.label_color,
+            ..Self::light()
+        };
+        colors.apply_style(&style);
+        colors
+    }
+
+    /// Overrides the colors with those set in `style`. The colors which are
+    /// not set in `style` are kept.
+    pub(crate) fn apply_style(&mut self, style: &WindowFrameStyle) {
+        if let Some(frame_color) = style.frame_color {
+            self.frame_base = frame_color;
+        }
+        if let Some(title) = style.title_bar_color {
+            self.button_face = title;
+            self.button_highlight = mix(title, color::WHITE, 0.5);
+            self.button_shadow = title.with_luminance(title.luminance() / 1.5);
+            self.set_title_color(title);
+        }
+        if let Some(title_text) = style.title_bar_text_color {
+            self.icon = title_text;
+            self.set_title_text_color(title_text);
+        }
+    }
+
+    /// Sets the title bar color of the focused window. The gradients of the
+    /// title bars are derived from it by fading towards the frame base color,
+    /// and the title bars of the other windows are also greyed out.
+    pub fn set_title_color(&mut self, title: Color) {
+        let inactive_title = mix(greyscale(title), self.frame_base, 0.5);
+        self.active_title = [title, mix(title, self.frame_base, 0.5)];
+        self.inactive_title = [inactive_title, mix(inactive_title, self.frame_base, 0.5)];
+    }
+
+    /// Sets the title text color of the focused window. The title text of
+    /// the other windows is greyed out and faded towards their title bars,
+    /// so this should be called after `set_title_color`.
+    pub fn set_title_text_color(&mut self, title_text: Color) {
+        self.active_title_text = title_text;
+        self.inactive_title_text = mix(greyscale(title_text), self.inactive_title[0], 0.4);
+    }
+
+    /// Gets the colors of the title bar, from left to right.
+    pub fn title(&self, is_focused: bool) -> [Color; 2] {
+        if is_focused {
+            self.active_title
+        } else {
+            self.inactive_title
+        }
+    }
+
+    pub fn title_text(&self, is_focused: bool) -> Color {
+        if is_focused {
+            self.active_title_text
+        } else {
+            self.inactive_title_text
+        }
+    }
+}
+
+impl Default for ColorScheme {
+    fn default() -> Self {
+        Self::light()
+    }
+}
+
+/// Mixes `b` into `a` by the fraction `t`.
+fn mix(a: Color, b: Color, t: f32) -> Color {
+    let (a, b) = (a.to_rgb(), b.to_rgb());
+    color::rgba(
+        a.0 + (b.0 - a.0) * t,
+        a.1 + (b.1 - a.1) * t,
+        a.2 + (b.2 - a.2) * t,
+        a.3 + (b.3 - a.3) * t,
+    )
+}
+
+fn greyscale(c: Color) -> Color {
+    let luminance = c.luminance();
+    color::rgba(luminance, luminance, luminance, c.to_rgb().3)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use conrod_core::theme::WidgetDefault;
+
+    #[test]
+    fn test_from_theme() {
+        let mut theme = Theme::default();
+        theme.background_color = color::GREY;
+        theme.shape_color = color::BLUE;
+        theme.label_color = color::WHITE;
+        let colors = ColorScheme::from_theme(&theme);
+        assert_eq!(colors.frame_base, color::GREY);
+        assert_eq!(colors.active_title[0], color::BLUE);
+        assert_eq!(colors.active_title_text, color::WHITE);
+        // The title bars keep a gradient, and the other windows are greyed
+        // out.
+        assert_ne!(colors.active_title[1], color::BLUE);
+        assert_ne!(colors.inactive_title[0], colors.inactive_title[1]);
+        assert_ne!(colors.inactive_title_text, color::WHITE);
+
+        // The widget styling for the window frames overrides the theme.
+        let style = WindowFrameStyle {
+            frame_color: Some(color::DARK_GREY),
+            title_bar_text_color: Some(color::YELLOW),
+            ..WindowFrameStyle::default()
+        };
+        theme.widget_styling.insert(
+            std::any::TypeId::of::<WindowFrameStyle>(),
+            WidgetDefault::new(Box::new(style)),
+        );
+        let colors = ColorScheme::from_theme(&theme);
+        assert_eq!(colors.frame_base, color::DARK_GREY);
+        assert_eq!(colors.active_title[0], color::BLUE);
+        assert_eq!(colors.active_title_text, color::YELLOW);
+        assert_eq!(colors.icon, color::YELLOW);
+    }
+
+    #[test]
+    fn test_apply_style() {
+        let mut colors = ColorScheme::dark();
+        colors.apply_style(&WindowFrameStyle {
+            title_bar_color: Some(color::RED),
+            ..WindowFrameStyle::default()
+        });
+        assert_eq!(colors.active_title[0], color::RED);
+        assert_eq!(colors.button_face, color::RED);
+        // The colors which are not set in the style are kept.
+        assert_eq!(colors.frame_base, ColorScheme::dark().frame_base);
+        assert_eq!(colors.icon, ColorScheme::dark().icon);
+    }
+}
@@ -0,0 +1,79 @@
+use conrod_core::{color, widget};
+
+/// The number of segments making up each rounded corner.
+const CORNER_SEGMENTS: usize = 6;
+
+/// Makes the outline of a rounded rectangle as a convex polygon, going
+/// counter-clockwise from the bottom-right corner.
+fn make_rounded_rect_polygon(
+    bottom_left: [f64; 2],
+    top_right: [f64; 2],
+    radius: f64,
+) -> Vec<[f64; 2]> {
+    let [x_o, y_o] = bottom_left;
+    let [x_e, y_e] = top_right;
+    let radius = radius
+        .min((x_e - x_o) / 2.0)
+        .min((y_e - y_o) / 2.0)
+        .max(0.0);
+    if radius == 0.0 {
+        return vec![[x_e, y_o], [x_e, y_e], [x_o, y_e], [x_o, y_o]];
+    }
+    // The centres of the corner arcs, each with the angle at which its arc
+    // starts.
+    let corners = [
+        ([x_e - radius, y_o + radius], -std::f64::consts::FRAC_PI_2),
+        ([x_e - radius, y_e - radius], 0.0),
+        ([x_o + radius, y_e - radius], std::f64::consts::FRAC_PI_2),
+        ([x_o + radius, y_o + radius], std::f64::consts::PI),
+    ];
+    let mut points = Vec::with_capacity(corners.len() * (CORNER_SEGMENTS + 1));
+    for &([c_x, c_y], start_angle) in &corners {
+        for i in 0..=CORNER_SEGMENTS {
+            let angle =
+                start_angle + std::f64::consts::FRAC_PI_2 * i as f64 / CORNER_SEGMENTS as f64;
+            points.push([c_x + radius * angle.cos(), c_y + radius * angle.sin()]);
+        }
+    }
+    points
+}
+
+/// Makes a filled rounded rectangle as a triangle fan around its centre.
+pub(super) fn make_rounded_rect(
+    bottom_left: [f64; 2],
+    top_right: [f64; 2],
+    radius: f64,
+    color: color::Color,
+) -> impl Iterator<Item = widget::triangles::Triangle<widget::triangles::ColoredPoint>> {
+    let [x_o, y_o] = bottom_left;
+    let [x_e, y_e] = top_right;
+    let centre = [(x_o + x_e) / 2.0, (y_o + y_e) / 2.0];
+    let color = color.to_rgb();
+    let points = make_rounded_rect_polygon(bottom_left, top_right, radius);
+    (0..points.len()).map(move |i| {
+        let next = points[(i + 1) % points.len()];
+        widget::triangles::Triangle([(centre, color), (points[i], color), (next, color)])
+    })
+}
+
+/// Makes a filled rounded rectangle with an outline of `line_thickness`
+/// drawn inside its bounds.
+pub(super) fn make_outlined_rounded_rect(
+    bottom_left: [f64; 2],
+    top_right: [f64; 2],
+    radius: f64,
+    line_thickness: f64,
+    outline_color: color::Color,
+    fill_color: color::Color,
+) -> impl Iterator<Item = widget::triangles::Triangle<widget::triangles::ColoredPoint>> {
+    let [x_o, y_o] = bottom_left;
+    let [x_e, y_e] = top_right;
+    let outline = make_rounded_rect(bottom_left, top_right, radius, outline_color);
+    let fill = make_rounded_rect(
+        [x_o + line_thickness, y_o + line_thickness],
+        [x_e - line_thickness, y_e - line_thickness],
+        radius - line_thickness,
+        fill_color,
+    );
+    outline.chain(fill)
+}
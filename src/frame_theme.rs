@@ -0,0 +1,333 @@
+use crate::{
+    classic_frame,
+    color_scheme::ColorScheme,
+    flat_frame, util,
+    windowing_area::layout::{FrameMetrics, FrameMetricsConfig},
+};
+
+use conrod_core::{widget, Color, Point, Rect};
+
+/// The triangles making up a part of the window decorations.
+pub type FrameTriangles = Vec<widget::triangles::Triangle<widget::triangles::ColoredPoint>>;
+
+/// The buttons on the title bar of a window.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ButtonType {
+    Collapse,
+    Uncollapse,
+    Maximize,
+    Restore,
+    Close,
+}
+
+/// Draws the decorations of the windows in a `WindowingArea`, which are the
+/// frame, the title bar, the tab strip and the title bar buttons, and decides
+/// their metrics. All rects are in conrod coordinates, and the decorations
+/// are drawn with the colors of the `ColorScheme`.
+pub trait FrameTheme: std::fmt::Debug {
+    /// Gets the metrics of the window decorations from the configured sizes
+    /// for the hidpi factor. The metrics are in logical pixels, aligned to
+    /// the physical pixel grid.
+    fn frame_metrics(&self, config: &FrameMetricsConfig, hidpi_factor: f64) -> FrameMetrics {
+        config.to_frame_metrics(hidpi_factor)
+    }
+
+    /// Makes the frame of a window filling `rect`, which is drawn below the
+    /// title bar, the tab strip and the client area.
+    fn frame(
+        &self,
+        rect: Rect,
+        colors: &ColorScheme,
+        frame_metrics: &FrameMetrics,
+        hidpi_factor: f64,
+    ) -> FrameTriangles;
+
+    /// Makes the background of the title bar filling `rect`.
+    fn title_bar(
+        &self,
+        rect: Rect,
+        colors: &ColorScheme,
+        is_focused: bool,
+        hidpi_factor: f64,
+    ) -> FrameTriangles;
+
+    /// Gets the color of the title text on the title bar.
+    fn title_text_color(&self, colors: &ColorScheme, is_focused: bool) -> Color {
+        colors.title_text(is_focused)
+    }
+
+    /// Makes a tab of the tab strip filling `rect`.
+    fn tab(
+        &self,
+        rect: Rect,
+        colors: &ColorScheme,
+        is_active: bool,
+        frame_metrics: &FrameMetrics,
+        hidpi_factor: f64,
+    ) -> FrameTriangles;
+
+    /// Gets the color of the title text of a tab.
+    fn tab_text_color(&self, colors: &ColorScheme, _is_active: bool) -> Color {
+        colors.frame_text
+    }
+
+    /// Makes a title bar button filling `rect`, including its icon.
+    fn button(
+        &self,
+        rect: Rect,
+        colors: &ColorScheme,
+        button_type: ButtonType,
+        is_pressed: bool,
+        hidpi_factor: f64,
+    ) -> FrameTriangles;
+}
+
+/// The default theme, which looks like the classic Windows 9x windows with
+/// bevelled borders and gradient title bars.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ClassicTheme;
+
+impl FrameTheme for ClassicTheme {
+    fn frame(
+        &self,
+        rect: Rect,
+        colors: &ColorScheme,
+        frame_metrics: &FrameMetrics,
+        _hidpi_factor: f64,
+    ) -> FrameTriangles {
+        classic_frame::make_panel_frame(
+            rect.bottom_left(),
+            rect.top_right(),
+            // The bevelled panel frame border is only half of
+            // `border_thickness`.
+            frame_metrics.border_thickness / 2.0,
+            colors.frame_base,
+            colors.button_highlight,
+            colors.button_shadow,
+        )
+        .collect()
+    }
+
+    fn title_bar(
+        &self,
+        rect: Rect,
+        colors: &ColorScheme,
+        is_focused: bool,
+        _hidpi_factor: f64,
+    ) -> FrameTriangles {
+        let [color_left, color_right] = colors.title(is_focused);
+        classic_frame::make_title_bar_gradient(
+            rect.bottom_left(),
+            rect.top_right(),
+            color_left,
+            color_right,
+        )
+        .collect()
+    }
+
+    fn tab(
+        &self,
+        rect: Rect,
+        colors: &ColorScheme,
+        is_active: bool,
+        frame_metrics: &FrameMetrics,
+        _hidpi_factor: f64,
+    ) -> FrameTriangles {
+        let base_color = colors.frame_base;
+        let tab_color = if is_active {
+            base_color
+        } else {
+            base_color.with_luminance(base_color.luminance() * 0.85)
+        };
+        classic_frame::make_panel_frame(
+            rect.bottom_left(),
+            rect.top_right(),
+            frame_metrics.border_thickness / 2.0,
+            tab_color,
+            colors.button_highlight,
+            colors.button_shadow,
+        )
+        .collect()
+    }
+
+    fn button(
+        &self,
+        rect: Rect,
+        colors: &ColorScheme,
+        button_type: ButtonType,
+        is_pressed: bool,
+        hidpi_factor: f64,
+    ) -> FrameTriangles {
+        let unit = pixel_unit(hidpi_factor);
+        let border_thickness = 2.0 * unit;
+        let mut triangles: FrameTriangles = classic_frame::make_button_frame(
+            rect.bottom_left(),
+            rect.top_right(),
+            border_thickness,
+            colors.button_face,
+            colors.button_highlight,
+            colors.button_shadow,
+            is_pressed,
+        )
+        .collect();
+
+        let click_shift = 1.0 * unit;
+        let icon_rect = {
+            let icon_rect = rect.pad(border_thickness);
+            if is_pressed {
+                icon_rect.pad_left(click_shift).pad_top(click_shift)
+            } else {
+                icon_rect.pad_right(click_shift).pad_bottom(click_shift)
+            }
+        };
+        triangles.extend(with_color(
+            button_icon(button_type, icon_rect, hidpi_factor),
+            colors.icon,
+        ));
+        triangles
+    }
+}
+
+/// A flat theme with solid title bars, rounded corners and thin outlines.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FlatTheme;
+
+impl FlatTheme {
+    fn outline_color(base_color: Color) -> Color {
+        base_color.with_luminance(base_color.luminance() * 0.6)
+    }
+}
+
+impl FrameTheme for FlatTheme {
+    fn frame(
+        &self,
+        rect: Rect,
+        colors: &ColorScheme,
+        _frame_metrics: &FrameMetrics,
+        hidpi_factor: f64,
+    ) -> FrameTriangles {
+        let unit = pixel_unit(hidpi_factor);
+        flat_frame::make_outlined_rounded_rect(
+            rect.bottom_left(),
+            rect.top_right(),
+            4.0 * unit,
+            unit,
+            Self::outline_color(colors.frame_base),
+            colors.frame_base,
+        )
+        .collect()
+    }
+
+    fn title_bar(
+        &self,
+        rect: Rect,
+        colors: &ColorScheme,
+        is_focused: bool,
+        _hidpi_factor: f64,
+    ) -> FrameTriangles {
+        let [_, color] = colors.title(is_focused);
+        flat_frame::make_rounded_rect(rect.bottom_left(), rect.top_right(), 0.0, color).collect()
+    }
+
+    fn tab(
+        &self,
+        rect: Rect,
+        colors: &ColorScheme,
+        is_active: bool,
+        _frame_metrics: &FrameMetrics,
+        hidpi_factor: f64,
+    ) -> FrameTriangles {
+        let unit = pixel_unit(hidpi_factor);
+        let base_color = colors.frame_base;
+        let tab_color = if is_active {
+            base_color
+        } else {
+            base_color.with_luminance(base_color.luminance() * 0.9)
+        };
+        flat_frame::make_outlined_rounded_rect(
+            rect.bottom_left(),
+            rect.top_right(),
+            2.0 * unit,
+            unit,
+            Self::outline_color(base_color),
+            tab_color,
+        )
+        .collect()
+    }
+
+    fn button(
+        &self,
+        rect: Rect,
+        colors: &ColorScheme,
+        button_type: ButtonType,
+        is_pressed: bool,
+        hidpi_factor: f64,
+    ) -> FrameTriangles {
+        let unit = pixel_unit(hidpi_factor);
+        let face_color = if is_pressed {
+            colors.button_shadow
+        } else {
+            colors.button_face
+        };
+        let mut triangles: FrameTriangles = flat_frame::make_outlined_rounded_rect(
+            rect.bottom_left(),
+            rect.top_right(),
+            2.0 * unit,
+            unit,
+            colors.button_shadow,
+            face_color,
+        )
+        .collect();
+        triangles.extend(with_color(
+            button_icon(button_type, rect.pad(2.0 * unit), hidpi_factor),
+            colors.icon,
+        ));
+        triangles
+    }
+}
+
+/// Gets the size of a logical pixel rounded to whole physical pixels, so that
+/// thin lines stay sharp at fractional hidpi factors.
+fn pixel_unit(hidpi_factor: f64) -> f64 {
+    util::hidpi_factor_int(hidpi_factor) / hidpi_factor
+}
+
+/// Makes the icon of a title bar button filling `rect`.
+pub(crate) fn button_icon(
+    button_type: ButtonType,
+    rect: Rect,
+    hidpi_factor: f64,
+) -> Vec<widget::triangles::Triangle<Point>> {
+    let (bottom_left, top_right) = (rect.bottom_left(), rect.top_right());
+    match button_type {
+        ButtonType::Collapse => {
+            classic_frame::make_collapse_button_icon(bottom_left, top_right, hidpi_factor).collect()
+        }
+        ButtonType::Uncollapse => {
+            classic_frame::make_uncollapse_button_icon(bottom_left, top_right, hidpi_factor)
+                .collect()
+        }
+        ButtonType::Maximize => {
+            classic_frame::make_maximize_button_icon(bottom_left, top_right, hidpi_factor).collect()
+        }
+        ButtonType::Restore => {
+            classic_frame::make_restore_button_icon(bottom_left, top_right, hidpi_factor).collect()
+        }
+        ButtonType::Close => {
+            classic_frame::make_close_button_icon(bottom_left, top_right, hidpi_factor).collect()
+        }
+    }
+}
+
+/// Colors the single-color triangles of an icon.
+pub(crate) fn with_color(
+    triangles: impl IntoIterator<Item = widget::triangles::Triangle<Point>>,
+    color: Color,
+) -> impl Iterator<Item = widget::triangles::Triangle<widget::triangles::ColoredPoint>> {
+    let color = color.to_rgb();
+    triangles
+        .into_iter()
+        .map(move |widget::triangles::Triangle([a, b, c])| {
+            widget::triangles::Triangle([(a, color), (b, color), (c, color)])
+        })
+}
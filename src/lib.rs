@@ -1,11 +1,20 @@
 pub mod windowing_area;
 
-mod classic_button;
 mod classic_frame;
+mod color_scheme;
 mod empty_widget;
+mod flat_frame;
+mod frame_theme;
+mod title_button;
 mod util;
 
+pub use color_scheme::ColorScheme;
+pub use frame_theme::{ButtonType, ClassicTheme, FlatTheme, FrameTheme, FrameTriangles};
 pub use windowing_area::{
-    layout::{WinId, WindowingState},
-    WindowBuilder, WindowEvent, WindowSetter, WindowingArea, WindowingContext,
+    layout::{
+        DockSide, FrameMetrics, FrameMetricsConfig, LayoutSnapshot, WinId, WindowLayer,
+        WindowingEvent, WindowingState,
+    },
+    TitleBarDoubleClick, WindowBuilder, WindowEvent, WindowFrameStyle, WindowSetter, WindowingArea,
+    WindowingContext,
 };
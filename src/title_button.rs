@@ -0,0 +1,129 @@
+use crate::{
+    color_scheme::ColorScheme,
+    frame_theme::{ButtonType, FrameTheme},
+};
+
+use conrod_core::{widget, widget_ids, Positionable, UiCell, Widget, WidgetCommon};
+use widget::button::TimesClicked;
+
+/// A title bar button drawn by the `FrameTheme`.
+#[derive(WidgetCommon)]
+pub struct TitleButton<'a> {
+    #[conrod(common_builder)]
+    pub common: widget::CommonBuilder,
+    pub button_type: ButtonType,
+    pub theme: &'a dyn FrameTheme,
+    pub colors: ColorScheme,
+    pub hidpi_factor: f64,
+}
+
+widget_ids! {
+    pub struct Ids {
+        triangles,
+    }
+}
+
+impl<'a> TitleButton<'a> {
+    pub fn new(
+        button_type: ButtonType,
+        theme: &'a dyn FrameTheme,
+        colors: ColorScheme,
+        hidpi_factor: f64,
+    ) -> Self {
+        TitleButton {
+            common: widget::CommonBuilder::default(),
+            button_type,
+            theme,
+            colors,
+            hidpi_factor,
+        }
+    }
+}
+
+impl<'a> Widget for TitleButton<'a> {
+    type State = Ids;
+    type Style = ();
+    type Event = TimesClicked;
+
+    fn init_state(&self, id_gen: conrod_core::widget::id::Generator) -> Self::State {
+        Ids::new(id_gen)
+    }
+
+    fn style(&self) -> Self::Style {}
+
+    fn update(self, args: conrod_core::widget::UpdateArgs<Self>) -> Self::Event {
+        let widget::UpdateArgs {
+            id,
+            state,
+            rect,
+            ui,
+            ..
+        } = args;
+        let state: &mut widget::State<Ids> = state;
+        let Self {
+            button_type,
+            theme,
+            colors,
+            hidpi_factor,
+            ..
+        } = self;
+
+        let (interaction, times_triggered) = interaction_and_times_triggered(id, ui);
+
+        let triangles = theme.button(
+            rect,
+            &colors,
+            button_type,
+            interaction == Interaction::Press,
+            hidpi_factor,
+        );
+        widget::Triangles::multi_color(triangles)
+            .with_bounding_rect(rect)
+            .middle_of(id)
+            .graphics_for(id)
+            .place_on_kid_area(false)
+            .set(state.triangles, ui);
+
+        TimesClicked(times_triggered)
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum Interaction {
+    Idle,
+    Hover,
+    Press,
+}
+
+fn interaction_and_times_triggered(button_id: widget::Id, ui: &UiCell) -> (Interaction, u16) {
+    let input = ui.widget_input(button_id);
+    let mouse_interaction = input.mouse().map_or(Interaction::Idle, |mouse| {
+        if mouse.buttons.left().is_down() {
+            if ui.global_input().current.widget_under_mouse == Some(button_id) {
+                Interaction::Press
+            } else {
+                Interaction::Idle
+            }
+        } else {
+            Interaction::Hover
+        }
+    });
+    let interaction = match mouse_interaction {
+        Interaction::Idle | Interaction::Hover => {
+            let is_touch_press = ui
+                .global_input()
+                .current
+                .touch
+                .values()
+                .any(|t| t.start.widget == Some(button_id) && t.widget == Some(button_id));
+            if is_touch_press {
+                Interaction::Press
+            } else {
+                mouse_interaction
+            }
+        }
+        Interaction::Press => Interaction::Press,
+    };
+    let times_triggered = (input.clicks().left().count() + input.taps().count()) as u16;
+    (interaction, times_triggered)
+}
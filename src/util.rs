@@ -19,3 +19,14 @@ pub fn win_rect_to_conrod_rect(win_rect: [f64; 4], win_area_rect: Rect) -> Rect
     let y2 = top - y - h;
     conrod_core::Rect::from_corners([x1, y1], [x2, y2])
 }
+
+/// Rounds the hidpi factor to the whole number of physical pixels that thin
+/// lines of the window frames are scaled by, so that they stay sharp at
+/// fractional hidpi factors.
+pub fn hidpi_factor_int(hidpi_factor: f64) -> f64 {
+    if hidpi_factor.fract() < 0.51 {
+        hidpi_factor.trunc()
+    } else {
+        hidpi_factor.trunc() + 1.0
+    }
+}
@@ -1,18 +1,30 @@
-use crate::{empty_widget::EmptyWidget, util};
-use layout::{FrameMetrics, WinId, WindowingState};
+use crate::{
+    color_scheme::ColorScheme,
+    empty_widget::EmptyWidget,
+    frame_theme::{ClassicTheme, FrameTheme},
+    util,
+};
+use layout::{
+    DockSide, FrameMetrics, FrameMetricsConfig, WinId, WindowLayer, WindowingEvent, WindowingState,
+};
 use window_frame::WindowFrame;
+use window_switcher::WindowSwitcher;
 
 use conrod_core::{
     cursor,
+    input::{Key, ModifierKey},
     position::{self, Place},
-    widget, widget_ids, Position, Positionable, Sizeable, Ui, UiCell, Widget, WidgetCommon,
-    WidgetStyle,
+    widget, widget_ids, Colorable, Position, Positionable, Sizeable, Ui, UiCell, Widget,
+    WidgetCommon, WidgetStyle,
 };
 
 pub mod layout;
 
 mod debug;
 mod window_frame;
+mod window_switcher;
+
+pub use window_frame::Style as WindowFrameStyle;
 
 #[derive(WidgetCommon)]
 pub struct WindowingArea<'a> {
@@ -22,11 +34,20 @@ pub struct WindowingArea<'a> {
     pub windowing_state: &'a mut WindowingState,
     pub hidpi_factor: f64,
     pub enable_debug: bool,
+    pub window_switcher_chord: Option<(ModifierKey, Key)>,
+    pub keyboard_adjust_chord: Option<(ModifierKey, Key)>,
+    pub keyboard_adjust_step: f32,
+    pub frame_theme: &'a dyn FrameTheme,
+    pub color_scheme: Option<ColorScheme>,
+    pub frame_metrics_config: Option<FrameMetricsConfig>,
 }
 
 pub struct State {
     ids: Ids,
     maybe_dragging_win: Option<bool>,
+    /// The index into `WindowingState::mru_wins` of the window selected in
+    /// the window switcher, if the switcher is shown.
+    switcher_selection: Option<usize>,
 }
 
 #[derive(Copy, Clone, Debug, Default, PartialEq, WidgetStyle)]
@@ -36,8 +57,11 @@ pub struct WindowingContext<'a> {
     windowing_area_id: widget::Id,
     windowing_area_rect: conrod_core::Rect,
     windowing_state: &'a mut WindowingState,
+    frame_theme: &'a dyn FrameTheme,
+    color_scheme: ColorScheme,
     frame_metrics: FrameMetrics,
     hidpi_factor: f64,
+    events: Vec<WindowingEvent>,
 }
 
 #[derive(Clone, Debug)]
@@ -46,17 +70,64 @@ pub struct WindowBuilder<'a> {
     pub initial_position: Option<[f32; 2]>,
     pub initial_size: Option<[f32; 2]>,
     pub min_size: Option<[f32; 2]>,
+    pub max_size: Option<[f32; 2]>,
+    pub is_resizable: Option<bool>,
+    pub aspect_ratio: Option<f32>,
+    pub is_movable: bool,
+    pub layer: Option<WindowLayer>,
+    pub is_modal: bool,
+    pub owner: Option<WinId>,
+    pub follows_owner: Option<bool>,
     pub is_hidden: bool,
     pub is_collapsible: bool,
+    pub is_maximizable: bool,
     pub is_closable: bool,
+    pub auto_close: bool,
     pub is_collapsed: Option<bool>,
+    pub is_maximized: Option<bool>,
+    pub docked: Option<DockSide>,
+    pub title_bar_double_click: TitleBarDoubleClick,
+    /// The theme which draws the decorations of this window, instead of the
+    /// theme of the `WindowingArea`.
+    pub frame_theme: Option<&'a dyn FrameTheme>,
+    /// The colors of the decorations of this window, instead of the colors
+    /// of the `WindowingArea`.
+    pub color_scheme: Option<ColorScheme>,
+    /// The style of the decorations of this window. The colors set in it
+    /// override the color scheme.
+    pub frame_style: WindowFrameStyle,
     _private: (),
 }
 
+/// The action to take when the title bar of a window is double-clicked.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TitleBarDoubleClick {
+    /// Toggles the collapsed state of the window, unless the window is not
+    /// collapsible or its collapsed state has been set explicitly.
+    Collapse,
+    /// Toggles the maximized state of the window, unless the window is not
+    /// maximizable or its maximized state has been set explicitly.
+    Maximize,
+    /// Does nothing. The double-clicks are not reported in the `WindowEvent`.
+    Ignore,
+    /// Does nothing, but the double-clicks are still reported in the
+    /// `WindowEvent` so that the application can handle them itself.
+    ReportOnly,
+}
+
 pub struct WindowEvent {
     pub collapse_clicked: widget::button::TimesClicked,
+    pub maximize_clicked: widget::button::TimesClicked,
     pub close_clicked: widget::button::TimesClicked,
     pub title_bar_double_click_count: u32,
+    /// Whether the window has gained the focus since it was last made.
+    pub focus_gained: bool,
+    /// Whether the window has lost the focus since it was last made,
+    /// including when it has been hidden.
+    pub focus_lost: bool,
+    /// Whether the close button has requested the window to be closed, for a
+    /// window with `WindowBuilder::auto_close`.
+    pub close_requested: bool,
 }
 
 pub struct WindowSetter {
@@ -67,9 +138,12 @@ pub struct WindowSetter {
 widget_ids! {
     struct Ids {
         capture_overlay,
+        modal_overlay,
         window_frames[],
         // window_titles[],
         window_contents[],
+        drop_target_preview,
+        window_switcher,
         debug,
     }
 }
@@ -82,6 +156,12 @@ impl<'a> WindowingArea<'a> {
             windowing_state,
             hidpi_factor,
             enable_debug: false,
+            window_switcher_chord: Some((ModifierKey::CTRL, Key::Tab)),
+            keyboard_adjust_chord: Some((ModifierKey::CTRL, Key::F7)),
+            keyboard_adjust_step: 8.0,
+            frame_theme: &ClassicTheme,
+            color_scheme: None,
+            frame_metrics_config: None,
         }
     }
 
@@ -89,6 +169,59 @@ impl<'a> WindowingArea<'a> {
         self.enable_debug = enabled;
         self
     }
+
+    /// Sets the key chord which cycles through the windows in
+    /// most-recently-used order, or `None` to disable keyboard window
+    /// switching. Defaults to Ctrl+Tab. Pressing the chord repeatedly while
+    /// holding its modifiers moves the selection (Shift moves it backwards),
+    /// releasing the modifiers brings the selected window to the top, and
+    /// Escape cancels switching. A chord without modifiers switches to the
+    /// selected window immediately.
+    pub fn window_switcher(mut self, chord: Option<(ModifierKey, Key)>) -> Self {
+        self.window_switcher_chord = chord;
+        self
+    }
+
+    /// Sets the key chord which starts moving and resizing the focused window
+    /// with the keyboard, or `None` to disable it. Defaults to Ctrl+F7. The
+    /// arrow keys move the window and Shift+arrow keys resize it, by the
+    /// step set with `keyboard_adjust_step` or by one physical pixel while
+    /// holding Ctrl. Enter or a mouse press finishes adjusting, and Escape
+    /// restores the window to where it was.
+    pub fn keyboard_adjust(mut self, chord: Option<(ModifierKey, Key)>) -> Self {
+        self.keyboard_adjust_chord = chord;
+        self
+    }
+
+    /// Sets the distance in logical pixels by which each arrow key press
+    /// moves or resizes a window with the keyboard. Defaults to 8.
+    pub fn keyboard_adjust_step(mut self, step: f32) -> Self {
+        self.keyboard_adjust_step = step;
+        self
+    }
+
+    /// Sets the theme which draws the window decorations and decides their
+    /// metrics. Defaults to `ClassicTheme`.
+    pub fn frame_theme(mut self, frame_theme: &'a dyn FrameTheme) -> Self {
+        self.frame_theme = frame_theme;
+        self
+    }
+
+    /// Sets the colors of the window decorations. By default, the colors are
+    /// taken from the conrod `Theme` with `ColorScheme::from_theme`. Use
+    /// `ColorScheme::light` for the colors of the classic look.
+    pub fn color_scheme(mut self, color_scheme: ColorScheme) -> Self {
+        self.color_scheme = Some(color_scheme);
+        self
+    }
+
+    /// Sets the sizes of the window decorations in logical pixels, which are
+    /// aligned to the physical pixel grid. This is the same as
+    /// `WindowingState::set_frame_metrics_config`.
+    pub fn frame_metrics(mut self, config: FrameMetricsConfig) -> Self {
+        self.frame_metrics_config = Some(config);
+        self
+    }
 }
 
 impl<'a> Widget for WindowingArea<'a> {
@@ -100,6 +233,7 @@ impl<'a> Widget for WindowingArea<'a> {
         State {
             ids: Ids::new(id_gen),
             maybe_dragging_win: None,
+            switcher_selection: None,
         }
     }
 
@@ -128,8 +262,15 @@ impl<'a> Widget for WindowingArea<'a> {
             windowing_state,
             hidpi_factor,
             enable_debug,
+            window_switcher_chord,
+            keyboard_adjust_chord,
+            keyboard_adjust_step,
+            frame_theme,
+            color_scheme,
+            frame_metrics_config,
             ..
         } = self;
+        let color_scheme = color_scheme.unwrap_or_else(|| ColorScheme::from_theme(ui.theme()));
 
         // Snap the rect inward to the physical pixel grid if needed.
         let rect = if rect.dim() == ui.window_dim() {
@@ -157,9 +298,9 @@ impl<'a> Widget for WindowingArea<'a> {
 
         // Remove the windows that weren't used in the last iteration.
         windowing_state.sweep_unneeded();
+        windowing_state.apply_close_requests();
 
-        let is_drag_move_window =
-            ui.global_input().current.modifiers == conrod_core::input::ModifierKey::ALT;
+        let is_drag_move_window = ui.global_input().current.modifiers == ModifierKey::ALT;
         if is_drag_move_window {
             // Add an empty widget on top for mouse capturing.
             EmptyWidget::new()
@@ -171,6 +312,23 @@ impl<'a> Widget for WindowingArea<'a> {
                 .set(state.ids.capture_overlay, &mut ui);
         }
 
+        let maybe_modal_win = windowing_state.modal_win();
+        if let Some(modal_win_id) = maybe_modal_win {
+            // Add an empty widget right below the modal window to block the
+            // mouse events to the other windows.
+            let modal_z_order = windowing_state
+                .win_z_order(modal_win_id)
+                .unwrap_or_else(|| unreachable!());
+            let modal_depth = -(modal_z_order as position::Depth);
+            EmptyWidget::new()
+                .graphics_for(id)
+                .place_on_kid_area(false)
+                .xy(rect.xy())
+                .wh(rect.dim())
+                .depth(modal_depth + 0.5)
+                .set(state.ids.modal_overlay, ui);
+        }
+
         if state.ids.window_frames.len() != windowing_state.win_count() {
             let target_len = windowing_state.win_count();
             state.update(|state| {
@@ -185,7 +343,14 @@ impl<'a> Widget for WindowingArea<'a> {
             });
         }
 
-        windowing_state.set_dimensions([rect.w() as f32, rect.h() as f32], hidpi_factor);
+        let frame_metrics_config =
+            frame_metrics_config.unwrap_or_else(|| windowing_state.frame_metrics_config());
+        windowing_state.set_dimensions_and_frame_metrics(
+            [rect.w() as f32, rect.h() as f32],
+            hidpi_factor,
+            frame_metrics_config,
+            frame_theme.frame_metrics(&frame_metrics_config, hidpi_factor),
+        );
         let frame_metrics = windowing_state.frame_metrics();
 
         let current_input = &ui.global_input().current;
@@ -193,37 +358,59 @@ impl<'a> Widget for WindowingArea<'a> {
             for event in ui.global_input().events().ui() {
                 match event {
                     conrod_core::event::Ui::Press(
-                        Some(press_id),
+                        maybe_press_id,
                         conrod_core::event::Press {
                             button: conrod_core::event::Button::Mouse(_, pos),
                             ..
                         },
                     ) => {
-                        let win_under_cursor =
-                            if is_drag_move_window {
-                                if *press_id == id {
-                                    let pos = util::conrod_point_to_layout_pos(*pos, rect);
-                                    windowing_state.win_hit_test(pos).map(|(win_id, _)| win_id)
-                                } else {
-                                    None
-                                }
+                        windowing_state.keyboard_adjust_end(false);
+                        let is_self_press = *maybe_press_id == Some(id);
+                        let win_under_cursor = if is_drag_move_window {
+                            if is_self_press {
+                                let pos = util::conrod_point_to_layout_pos(*pos, rect);
+                                windowing_state.win_hit_test(pos).map(|(win_id, _)| win_id)
                             } else {
-                                state.ids.window_frames.iter().enumerate().find_map(
-                                    |(i, &frame_id)| {
-                                        if frame_id == *press_id
-                                            || ui.widget_graph().does_recursive_depth_edge_exist(
-                                                frame_id, *press_id,
-                                            )
-                                        {
-                                            Some(WinId(i as u32))
-                                        } else {
-                                            None
-                                        }
-                                    },
-                                )
-                            };
-                        if let Some(win_id) = win_under_cursor {
-                            windowing_state.bring_to_top(win_id);
+                                None
+                            }
+                        } else {
+                            state
+                                .ids
+                                .window_frames
+                                .iter()
+                                .enumerate()
+                                .find_map(|(i, &frame_id)| {
+                                    if maybe_press_id.is_some_and(|press_id| {
+                                        frame_id == press_id
+                                            || ui
+                                                .widget_graph()
+                                                .does_recursive_depth_edge_exist(frame_id, press_id)
+                                    }) {
+                                        windowing_state.win_id_at(i)
+                                    } else {
+                                        None
+                                    }
+                                })
+                        };
+                        match maybe_modal_win {
+                            Some(modal_win_id)
+                                if win_under_cursor != Some(modal_win_id)
+                                    && (win_under_cursor.is_some() || is_self_press) =>
+                            {
+                                // Presses outside of the modal window are
+                                // blocked.
+                                windowing_state.flash_win(modal_win_id);
+                                ui.needs_redraw();
+                            }
+                            _ => match win_under_cursor {
+                                Some(win_id) => windowing_state.focus_and_bring_to_top(win_id),
+                                None if rect.is_over(*pos) => {
+                                    // Clicking on the backdrop removes the
+                                    // focus from all windows.
+                                    windowing_state.set_focused_win(None);
+                                }
+                                None => {}
+                            },
                         }
                     }
                     conrod_core::event::Ui::Drag(Some(drag_id), drag)
@@ -238,22 +425,23 @@ impl<'a> Widget for WindowingArea<'a> {
                                         .current_dragging_win()
                                         .map(|(win_id, _)| win_id)
                                 } else {
-                                    // Otherwise, use the topmost window.
-                                    windowing_state.topmost_win()
+                                    // Otherwise, use the window that has just
+                                    // been pressed.
+                                    windowing_state.active_win()
                                 }
-                                .map_or(false, |WinId(win)| {
-                                    *drag_id == state.ids.window_frames[win as usize]
+                                .is_some_and(|win_id| {
+                                    *drag_id == state.ids.window_frames[win_id.index()]
                                 })
                             }
                         };
                         if drag.button == conrod_core::input::MouseButton::Left && is_self_event() {
-                            let topmost_win_id = windowing_state
-                                .topmost_win()
+                            let active_win_id = windowing_state
+                                .active_win()
                                 .unwrap_or_else(|| unreachable!());
                             let is_dragging_win = state.maybe_dragging_win.unwrap_or_else(|| {
                                 let pos = util::conrod_point_to_layout_pos(drag.origin, rect);
                                 let ht = windowing_state
-                                    .specific_win_hit_test(topmost_win_id, pos)
+                                    .specific_win_hit_test(active_win_id, pos)
                                     .map(|ht| {
                                         if is_drag_move_window {
                                             layout::HitTest::TitleBarOrDragArea
@@ -261,16 +449,30 @@ impl<'a> Widget for WindowingArea<'a> {
                                             ht
                                         }
                                     });
-                                if let Some(ht) = ht {
-                                    windowing_state.win_drag_start(topmost_win_id, ht)
-                                } else {
-                                    false
+                                match ht {
+                                    Some(layout::HitTest::TabStrip) => {
+                                        // Drag the tab under the pointer.
+                                        match windowing_state.win_tab_at(active_win_id, pos) {
+                                            Some(tab_win_id) => windowing_state.win_drag_start(
+                                                tab_win_id,
+                                                layout::HitTest::TabStrip,
+                                            ),
+                                            None => false,
+                                        }
+                                    }
+                                    Some(ht) => windowing_state.win_drag_start(active_win_id, ht),
+                                    None => false,
                                 }
                             });
                             let new_is_dragging_win = if is_dragging_win {
                                 let drag_delta_x = (drag.to[0] - drag.origin[0]) as f32;
                                 let drag_delta_y = -(drag.to[1] - drag.origin[1]) as f32;
-                                windowing_state.win_drag_update([drag_delta_x, drag_delta_y])
+                                let is_dragging =
+                                    windowing_state.win_drag_update([drag_delta_x, drag_delta_y]);
+                                windowing_state.win_drag_set_pointer_pos(
+                                    util::conrod_point_to_layout_pos(drag.to, rect),
+                                );
+                                is_dragging
                             } else {
                                 false
                             };
@@ -301,10 +503,110 @@ impl<'a> Widget for WindowingArea<'a> {
                             });
                         }
                     }
+                    conrod_core::event::Ui::Press(
+                        _,
+                        conrod_core::event::Press {
+                            button: conrod_core::event::Button::Keyboard(key),
+                            modifiers,
+                        },
+                    ) if windowing_state.keyboard_adjusting_win().is_some() => {
+                        let direction = match key {
+                            Key::Left => Some([-1.0, 0.0]),
+                            Key::Right => Some([1.0, 0.0]),
+                            Key::Up => Some([0.0, -1.0]),
+                            Key::Down => Some([0.0, 1.0]),
+                            _ => None,
+                        };
+                        match key {
+                            Key::Escape => windowing_state.keyboard_adjust_end(true),
+                            Key::Return | Key::NumPadEnter => {
+                                windowing_state.keyboard_adjust_end(false)
+                            }
+                            _ => {
+                                if let Some([dx, dy]) = direction {
+                                    let distance = if modifiers.contains(ModifierKey::CTRL) {
+                                        (1.0 / hidpi_factor) as f32
+                                    } else {
+                                        keyboard_adjust_step
+                                    };
+                                    windowing_state.keyboard_adjust_by(
+                                        modifiers.contains(ModifierKey::SHIFT),
+                                        [dx * distance, dy * distance],
+                                    );
+                                }
+                            }
+                        }
+                    }
+                    conrod_core::event::Ui::Press(
+                        _,
+                        conrod_core::event::Press {
+                            button: conrod_core::event::Button::Keyboard(key),
+                            modifiers,
+                        },
+                    ) => match window_switcher_chord {
+                        Some((chord_modifiers, chord_key))
+                            if *key == chord_key
+                                && modifiers.contains(chord_modifiers)
+                                && maybe_modal_win.is_none() =>
+                        {
+                            let win_count = windowing_state.mru_wins().count();
+                            if win_count > 0 {
+                                // Shift cycles backwards unless it is part of
+                                // the chord.
+                                let is_reverse = modifiers.contains(ModifierKey::SHIFT)
+                                    && !chord_modifiers.contains(ModifierKey::SHIFT);
+                                let selection = match state.switcher_selection {
+                                    Some(i) if is_reverse => (i + win_count - 1) % win_count,
+                                    Some(i) => (i + 1) % win_count,
+                                    None if is_reverse => win_count - 1,
+                                    None => 1 % win_count,
+                                };
+                                state.update(|state| {
+                                    state.switcher_selection = Some(selection);
+                                });
+                            }
+                        }
+                        _ if keyboard_adjust_chord.is_some_and(
+                            |(chord_modifiers, chord_key)| {
+                                *key == chord_key && modifiers.contains(chord_modifiers)
+                            },
+                        ) =>
+                        {
+                            if let Some(win_id) =
+                                maybe_modal_win.or_else(|| windowing_state.focused_win())
+                            {
+                                windowing_state.win_keyboard_adjust_start(win_id);
+                            }
+                        }
+                        _ if *key == Key::Escape && state.switcher_selection.is_some() => {
+                            state.update(|state| {
+                                state.switcher_selection = None;
+                            });
+                        }
+                        _ => {}
+                    },
                     _ => {}
                 }
             }
-            if state.maybe_dragging_win != Some(true) {
+            if let Some(selection) = state.switcher_selection {
+                // The selected window is brought to the top once the modifiers
+                // of the chord are released.
+                let is_chord_held = window_switcher_chord.is_some_and(|(chord_modifiers, _)| {
+                    !chord_modifiers.is_empty() && current_input.modifiers.contains(chord_modifiers)
+                });
+                if !is_chord_held {
+                    let maybe_win_id = windowing_state.mru_wins().nth(selection);
+                    if let Some(win_id) = maybe_win_id {
+                        windowing_state.focus_and_bring_to_top(win_id);
+                    }
+                    state.update(|state| {
+                        state.switcher_selection = None;
+                    });
+                }
+            }
+            if state.maybe_dragging_win != Some(true)
+                && windowing_state.keyboard_adjusting_win().is_none()
+            {
                 windowing_state.ensure_all_win_in_area();
             }
         }
@@ -331,12 +633,38 @@ impl<'a> Widget for WindowingArea<'a> {
                                 if is_drag_move_window {
                                     mouse_widget == id
                                 } else {
-                                    mouse_widget == state.ids.window_frames[win_id.0 as usize]
+                                    mouse_widget == state.ids.window_frames[win_id.index()]
                                 }
                             })
                             .map(|(win_id, ht)| match ht {
-                                _ if is_drag_move_window => layout::HitTest::TitleBarOrDragArea,
-                                layout::HitTest::TitleBarOrDragArea => ht,
+                                _ if windowing_state.win_docked_side(win_id).is_some()
+                                    || (windowing_state.win_is_tiled(win_id)
+                                        && !windowing_state.win_is_maximized(win_id)) =>
+                                {
+                                    // Docked and tiled windows can only be
+                                    // resized with the splitters.
+                                    match ht {
+                                        _ if is_drag_move_window => layout::HitTest::Content,
+                                        layout::HitTest::TitleBarOrDragArea => {
+                                            layout::HitTest::Content
+                                        }
+                                        _ => ht,
+                                    }
+                                }
+                                _ if windowing_state.win_is_maximized(win_id) => {
+                                    // Can't move or resize maximized windows.
+                                    layout::HitTest::Content
+                                }
+                                _ if is_drag_move_window
+                                    || ht == layout::HitTest::TitleBarOrDragArea =>
+                                {
+                                    if windowing_state.win_is_pinned(win_id) {
+                                        // Can't move pinned windows.
+                                        layout::HitTest::Content
+                                    } else {
+                                        layout::HitTest::TitleBarOrDragArea
+                                    }
+                                }
                                 _ if windowing_state.win_is_collapsed(win_id) => {
                                     // Can't resize collapsed windows.
                                     layout::HitTest::Content
@@ -346,7 +674,7 @@ impl<'a> Widget for WindowingArea<'a> {
                     })
             })
             .and_then(|ht| match ht {
-                layout::HitTest::Content => None,
+                layout::HitTest::Content | layout::HitTest::TabStrip => None,
                 layout::HitTest::TitleBarOrDragArea => Some(cursor::MouseCursor::Grab),
                 layout::HitTest::TopBorder | layout::HitTest::BottomBorder => {
                     Some(cursor::MouseCursor::ResizeVertical)
@@ -365,10 +693,48 @@ impl<'a> Widget for WindowingArea<'a> {
             ui.set_mouse_cursor(cursor);
         }
 
+        // Show where the dragged window will be merged into a tab group.
+        if let Some(target_rect) = windowing_state
+            .current_drop_target()
+            .and_then(|win_id| windowing_state.win_display_rect_f64(win_id))
+        {
+            let target_rect = util::win_rect_to_conrod_rect(target_rect, rect);
+            widget::Rectangle::fill(target_rect.dim())
+                .color(conrod_core::color::rgba(0.05, 0.5, 0.8, 0.35))
+                .xy(target_rect.xy())
+                .graphics_for(id)
+                .place_on_kid_area(false)
+                .depth(position::Depth::MIN)
+                .set(state.ids.drop_target_preview, ui);
+        }
+
+        // Show the window switcher.
+        if let Some(selection) = state.switcher_selection {
+            let titles: Vec<&str> = windowing_state
+                .mru_wins()
+                .map(|win_id| windowing_state.win_title(win_id).unwrap_or(""))
+                .collect();
+            let [w, h] = WindowSwitcher::dim_for(frame_metrics, titles.len());
+            WindowSwitcher::new(
+                &titles,
+                selection,
+                frame_theme,
+                color_scheme,
+                frame_metrics,
+                hidpi_factor,
+            )
+            .graphics_for(id)
+            .place_on_kid_area(false)
+            .xy(rect.xy())
+            .w_h(w.min(rect.w()), h.min(rect.h()))
+            .depth(position::Depth::MIN)
+            .set(state.ids.window_switcher, ui);
+        }
+
         windowing_state.set_all_needed(false);
 
         if enable_debug {
-            if let Some(win_id) = windowing_state.topmost_win() {
+            if let Some(win_id) = windowing_state.active_win() {
                 debug::DebugWidget::new(&*windowing_state, win_id, hidpi_factor)
                     .graphics_for(id)
                     .place_on_kid_area(false)
@@ -378,12 +744,16 @@ impl<'a> Widget for WindowingArea<'a> {
                     .set(state.ids.debug, &mut ui);
             }
         }
+        let events = windowing_state.take_events();
         WindowingContext {
             windowing_area_id: id,
             windowing_area_rect: rect,
             windowing_state,
+            frame_theme,
+            color_scheme,
             frame_metrics,
             hidpi_factor,
+            events,
         }
     }
 
@@ -403,10 +773,26 @@ impl<'a> WindowBuilder<'a> {
             initial_position: None,
             initial_size: None,
             min_size: None,
+            max_size: None,
+            is_resizable: None,
+            aspect_ratio: None,
+            is_movable: true,
+            layer: None,
+            is_modal: false,
+            owner: None,
+            follows_owner: None,
             is_hidden: false,
             is_collapsible: true,
+            is_maximizable: false,
             is_closable: false,
+            auto_close: false,
             is_collapsed: None,
+            is_maximized: None,
+            docked: None,
+            title_bar_double_click: TitleBarDoubleClick::Collapse,
+            frame_theme: None,
+            color_scheme: None,
+            frame_style: WindowFrameStyle::default(),
             _private: (),
         }
     }
@@ -436,6 +822,85 @@ impl<'a> WindowBuilder<'a> {
         }
     }
 
+    /// Sets the maximum client size of the window. Otherwise the maximum size
+    /// set with `WindowingState::set_win_max_size` is left unchanged.
+    pub fn max_size(self, max_size: [f32; 2]) -> Self {
+        Self {
+            max_size: Some(max_size),
+            ..self
+        }
+    }
+
+    /// Sets whether the window can be resized by dragging its borders. A
+    /// window which is not resizable keeps the size it was created with.
+    /// Otherwise the value set with `WindowingState::set_win_resizable` is
+    /// left unchanged.
+    pub fn is_resizable(self, is_resizable: bool) -> Self {
+        Self {
+            is_resizable: Some(is_resizable),
+            ..self
+        }
+    }
+
+    /// Locks the aspect ratio (width / height) of the client area of the
+    /// window when it is resized by dragging its borders. Otherwise the aspect
+    /// ratio set with `WindowingState::set_win_aspect_ratio` is left
+    /// unchanged.
+    pub fn aspect_ratio(self, aspect_ratio: f32) -> Self {
+        Self {
+            aspect_ratio: Some(aspect_ratio),
+            ..self
+        }
+    }
+
+    /// Sets whether the window can be moved by dragging. Setting this to
+    /// `false` pins the window in place, the same as
+    /// `WindowingState::set_win_pinned`. Otherwise the pinned state set with
+    /// `WindowingState::set_win_pinned` is left unchanged.
+    pub fn is_movable(self, is_movable: bool) -> Self {
+        Self { is_movable, ..self }
+    }
+
+    /// Sets the z-order layer of the window. Windows in a higher layer are
+    /// always kept above the windows in lower layers. Otherwise the layer set
+    /// with `WindowingState::set_win_layer` is left unchanged.
+    pub fn layer(self, layer: WindowLayer) -> Self {
+        Self {
+            layer: Some(layer),
+            ..self
+        }
+    }
+
+    /// Sets whether the window is modal. While a modal window is shown, it is
+    /// kept above all other windows, and presses on the other windows are
+    /// blocked and flash the title bar of the modal window instead. This is
+    /// only applied when it changes, so `WindowingState::set_win_modal` can
+    /// still be used to change it in between.
+    pub fn is_modal(self, is_modal: bool) -> Self {
+        Self { is_modal, ..self }
+    }
+
+    /// Sets the owner of the window. Owned windows are kept above their owner,
+    /// are hidden while their owner is hidden or collapsed, and are removed
+    /// when their owner is no longer used. Otherwise the owner set with
+    /// `WindowingState::set_win_owner` is left unchanged.
+    pub fn owner(self, owner: WinId) -> Self {
+        Self {
+            owner: Some(owner),
+            ..self
+        }
+    }
+
+    /// Sets whether the window is moved along when its owner is dragged.
+    /// Otherwise the value set with `WindowingState::set_win_follows_owner`
+    /// is left unchanged.
+    pub fn follows_owner(self, follows_owner: bool) -> Self {
+        Self {
+            follows_owner: Some(follows_owner),
+            ..self
+        }
+    }
+
     pub fn is_hidden(self, is_hidden: bool) -> Self {
         Self { is_hidden, ..self }
     }
@@ -447,10 +912,18 @@ impl<'a> WindowBuilder<'a> {
         }
     }
 
+    /// Sets whether this window should have a maximize button on its frame.
+    pub fn is_maximizable(self, is_maximizable: bool) -> Self {
+        Self {
+            is_maximizable,
+            ..self
+        }
+    }
+
     /// Sets whether this window should have a close button on its frame. Note
     /// that the close button does nothing by default and you will need to
     /// handle the event yourself by using the `WindowEvent` data returned by
-    /// `WindowingContext::make_window`.
+    /// `WindowingContext::make_window`, unless `auto_close` is set.
     pub fn is_closable(self, is_closable: bool) -> Self {
         Self {
             is_closable,
@@ -458,10 +931,22 @@ impl<'a> WindowBuilder<'a> {
         }
     }
 
+    /// Sets whether clicking the close button requests the window to be
+    /// closed with `WindowingState::request_win_close`. The request is
+    /// reported in `WindowEvent::close_requested`, and as a
+    /// `WindowingEvent::CloseRequested` in the `WindowingContext::events` of
+    /// the next update. It can be vetoed with `WindowingContext::veto_close`
+    /// until the end of the frame in which the event is reported. Otherwise,
+    /// the window is closed on the update after that and listed in
+    /// `WindowingContext::closed_wins`.
+    pub fn auto_close(self, auto_close: bool) -> Self {
+        Self { auto_close, ..self }
+    }
+
     /// Sets whether the window is collapsed. Note that if the collapsed status
     /// has not been set explicitly, the `WindowingContext` will automatically
-    /// toggle the collapsed state when the collapse button is pressed or the
-    /// title bar is double-clicked. If you require explicitly setting the
+    /// toggle the collapsed state when the collapse button is pressed or (by
+    /// default) the title bar is double-clicked. If you require explicitly setting the
     /// collapse state of the window, you should handle these events yourself
     /// by using the `WindowEvent` data returned by `WindowingContext::make_window`.
     pub fn collapse(self, is_collapsed: bool) -> Self {
@@ -470,15 +955,109 @@ impl<'a> WindowBuilder<'a> {
             ..self
         }
     }
+
+    /// Docks the window to a side of the windowing area. Docked windows
+    /// reserve space along the edge so that floating windows are kept in the
+    /// remaining work area. They can only be resized by dragging their inner
+    /// border, and cannot be collapsed or maximized. The side is only applied
+    /// when it changes, so a window undocked with
+    /// `WindowingState::set_win_normal_rect` stays floating until then.
+    pub fn dock(self, side: DockSide) -> Self {
+        Self {
+            docked: Some(side),
+            ..self
+        }
+    }
+
+    /// Sets what to do when the title bar is double-clicked. By default, a
+    /// double-click toggles the collapsed state of the window.
+    pub fn title_bar_double_click(self, title_bar_double_click: TitleBarDoubleClick) -> Self {
+        Self {
+            title_bar_double_click,
+            ..self
+        }
+    }
+
+    /// Sets whether the window is maximized. Similar to `collapse`, if the
+    /// maximized state has not been set explicitly, the `WindowingContext`
+    /// will automatically toggle the maximized state when the maximize button
+    /// is pressed or, if the window is set to `TitleBarDoubleClick::Maximize`,
+    /// the title bar is double-clicked.
+    pub fn maximize(self, is_maximized: bool) -> Self {
+        Self {
+            is_maximized: Some(is_maximized),
+            ..self
+        }
+    }
+
+    /// Sets the theme which draws the decorations of this window, overriding
+    /// the theme set with `WindowingArea::frame_theme`. The frame metrics are
+    /// still decided by the theme of the `WindowingArea`, so that all windows
+    /// share the same layout.
+    pub fn frame_theme(self, frame_theme: &'a dyn FrameTheme) -> Self {
+        Self {
+            frame_theme: Some(frame_theme),
+            ..self
+        }
+    }
+
+    /// Sets the colors of the decorations of this window, overriding the
+    /// colors set with `WindowingArea::color_scheme`.
+    pub fn color_scheme(self, color_scheme: ColorScheme) -> Self {
+        Self {
+            color_scheme: Some(color_scheme),
+            ..self
+        }
+    }
+
+    /// Sets the style of the decorations of this window. The colors set in
+    /// the style override the color scheme of this window.
+    pub fn frame_style(self, frame_style: WindowFrameStyle) -> Self {
+        Self {
+            frame_style,
+            ..self
+        }
+    }
 }
 
 impl<'a> WindowingContext<'a> {
+    /// Gets the `WinId` of the window identified by `key`. This is the same as
+    /// `WindowingState::id_for_key`, provided here since the `WindowingState`
+    /// is borrowed by the `WindowingContext`.
+    pub fn id_for_key(&mut self, key: &str) -> WinId {
+        self.windowing_state.id_for_key(key)
+    }
+
+    /// Vetoes the pending close request of the window. This is the same as
+    /// `WindowingState::veto_win_close`.
+    pub fn veto_close(&mut self, win_id: WinId) {
+        self.windowing_state.veto_win_close(win_id);
+    }
+
+    /// Gets the windows closed in this update. This is the same as
+    /// `WindowingState::closed_wins`.
+    pub fn closed_wins(&self) -> &[WinId] {
+        self.windowing_state.closed_wins()
+    }
+
+    /// Gets the events which have happened to the windows since the last
+    /// update of the `WindowingArea`. Changes made while making the windows
+    /// in this update are reported in the next update.
+    pub fn events(&self) -> &[WindowingEvent] {
+        &self.events
+    }
+
     pub fn make_window<'c>(
         &mut self,
         builder: WindowBuilder,
         win_id: WinId,
         ui: &mut UiCell,
     ) -> (WindowEvent, Option<WindowSetter>) {
+        if !self.windowing_state.win_id_is_valid(win_id) {
+            // The window has already been destroyed. Don't let the stale
+            // `WinId` alias any other window reusing the same slot.
+            return (WindowEvent::none(), None);
+        }
         self.windowing_state
             .ensure_init(win_id, || layout::WindowInitialState {
                 client_size: builder.initial_size.unwrap_or_else(|| [200.0, 200.0]),
@@ -487,9 +1066,47 @@ impl<'a> WindowingContext<'a> {
                 is_collapsed: false,
             });
         self.windowing_state.set_needed(win_id, true);
+        self.windowing_state.set_win_title(win_id, builder.title);
+        self.windowing_state
+            .set_win_docked_from_builder(win_id, builder.docked);
+        let builder = if self.windowing_state.win_docked_side(win_id).is_some() {
+            WindowBuilder {
+                is_collapsible: false,
+                is_maximizable: false,
+                ..builder
+            }
+        } else {
+            builder
+        };
         if let Some(min_size) = builder.min_size {
             self.windowing_state.set_win_min_size(win_id, min_size);
         }
+        if let Some(max_size) = builder.max_size {
+            self.windowing_state
+                .set_win_max_size(win_id, Some(max_size));
+        }
+        if let Some(is_resizable) = builder.is_resizable {
+            self.windowing_state.set_win_resizable(win_id, is_resizable);
+        }
+        if let Some(aspect_ratio) = builder.aspect_ratio {
+            self.windowing_state
+                .set_win_aspect_ratio(win_id, Some(aspect_ratio));
+        }
+        if !builder.is_movable {
+            self.windowing_state.set_win_pinned(win_id, true);
+        }
+        if let Some(layer) = builder.layer {
+            self.windowing_state.set_win_layer(win_id, layer);
+        }
+        self.windowing_state
+            .set_win_modal_from_builder(win_id, builder.is_modal);
+        if let Some(owner) = builder.owner {
+            self.windowing_state.set_win_owner(win_id, Some(owner));
+        }
+        if let Some(follows_owner) = builder.follows_owner {
+            self.windowing_state
+                .set_win_follows_owner(win_id, follows_owner);
+        }
         if builder.is_collapsible {
             if let Some(is_collapsed) = builder.is_collapsed {
                 self.windowing_state.set_win_collapsed(win_id, is_collapsed);
@@ -497,17 +1114,30 @@ impl<'a> WindowingContext<'a> {
         } else {
             self.windowing_state.set_win_collapsed(win_id, false);
         }
+        if builder.is_maximizable {
+            if let Some(is_maximized) = builder.is_maximized {
+                self.windowing_state.set_win_maximized(win_id, is_maximized);
+            }
+        } else {
+            self.windowing_state.set_win_maximized(win_id, false);
+        }
         self.windowing_state
             .set_win_hidden(win_id, builder.is_hidden);
-        if builder.is_hidden {
-            return (
-                WindowEvent {
-                    collapse_clicked: widget::button::TimesClicked(0),
-                    close_clicked: widget::button::TimesClicked(0),
-                    title_bar_double_click_count: 0,
-                },
-                None,
-            );
+        let (focus_gained, focus_lost) = self.windowing_state.win_take_focus_change(win_id);
+        if builder.is_hidden
+            || self.windowing_state.win_is_closed(win_id)
+            || !self.windowing_state.win_is_active_tab(win_id)
+            || self.windowing_state.win_is_hidden_by_owner(win_id)
+        {
+            // Inactive tabs are shown only in the tab strip of the active tab,
+            // owned windows are hidden together with their owners, and closed
+            // windows are hidden until they are reopened.
+            let event = WindowEvent {
+                focus_gained,
+                focus_lost,
+                ..WindowEvent::none()
+            };
+            return (event, None);
         }
 
         let state: &State = match ui
@@ -521,43 +1151,88 @@ impl<'a> WindowingContext<'a> {
                 if cfg!(debug_assertions) {
                     panic!("Expected to get the widget state of `WindowingArea` without fail");
                 }
-                return (
-                    WindowEvent {
-                        collapse_clicked: widget::button::TimesClicked(0),
-                        close_clicked: widget::button::TimesClicked(0),
-                        title_bar_double_click_count: 0,
-                    },
-                    None,
-                );
+                return (WindowEvent::none(), None);
             }
         };
-        let win_idx = win_id.0 as usize;
+        let win_idx = win_id.index();
         let window_frame_id = state.ids.window_frames[win_idx];
         let content_widget_id = state.ids.window_contents[win_idx];
-        let window_depth = -(self.windowing_state.win_z_order(win_id) as position::Depth);
+        let z_order = self
+            .windowing_state
+            .win_z_order(win_id)
+            .unwrap_or_else(|| unreachable!());
+        let window_depth = -(z_order as position::Depth);
         let window_is_collapsed = self.windowing_state.win_is_collapsed(win_id);
+        let window_is_maximized = self.windowing_state.win_is_maximized(win_id);
         let conrod_window_rect = util::win_rect_to_conrod_rect(
             self.windowing_state
                 .win_display_rect_f64(win_id)
                 .expect("Window must have already been initialized"),
             self.windowing_area_rect,
         );
-        let is_focused = self.windowing_state.topmost_win() == Some(win_id);
-        let event = WindowFrame::new(self.frame_metrics, self.hidpi_factor)
-            .title(builder.title)
-            .is_focused(is_focused)
-            .is_collapsed(window_is_collapsed)
-            .is_collapsible(builder.is_collapsible)
-            .is_closable(builder.is_closable)
-            .frame_color(conrod_core::color::rgba(0.75, 0.75, 0.75, 1.0))
-            .title_bar_color(conrod_core::color::LIGHT_GRAY)
-            .xy(conrod_window_rect.xy())
-            .wh(conrod_window_rect.dim())
-            .depth(window_depth)
-            .parent(self.windowing_area_id)
-            .set(window_frame_id, ui);
-
-        let title_bar_double_click_count = ui
+        let is_focused = match self.windowing_state.win_flash_highlight(win_id) {
+            Some(is_highlighted) => {
+                // Keep updating until the flashing ends.
+                ui.needs_redraw();
+                is_highlighted
+            }
+            None => self.windowing_state.win_is_focused(win_id),
+        };
+        let tabs = self.windowing_state.win_tab_group(win_id).unwrap_or(&[]);
+        let active_tab_idx = tabs.iter().position(|&tab| tab == win_id).unwrap_or(0);
+        let tab_titles: Vec<&str> = tabs
+            .iter()
+            .map(|&tab| self.windowing_state.win_title(tab).unwrap_or(""))
+            .collect();
+        let frame_theme = builder.frame_theme.unwrap_or(self.frame_theme);
+        let color_scheme = builder.color_scheme.unwrap_or(self.color_scheme);
+        let event = WindowFrame::new(
+            frame_theme,
+            color_scheme,
+            self.frame_metrics,
+            self.hidpi_factor,
+        )
+        .with_style(builder.frame_style)
+        .title(builder.title)
+        .tabs(&tab_titles, active_tab_idx)
+        .is_focused(is_focused)
+        .is_collapsed(window_is_collapsed)
+        .is_collapsible(builder.is_collapsible)
+        .is_maximized(window_is_maximized)
+        .is_maximizable(builder.is_maximizable)
+        .is_closable(builder.is_closable)
+        .xy(conrod_window_rect.xy())
+        .wh(conrod_window_rect.dim())
+        .depth(window_depth)
+        .parent(self.windowing_area_id)
+        .set(window_frame_id, ui);
+
+        // Switch to the clicked tab.
+        let clicked_tab = ui
+            .global_input()
+            .events()
+            .ui()
+            .filter_map(|event| match event {
+                conrod_core::event::Ui::Click(
+                    Some(click_id),
+                    conrod_core::event::Click {
+                        button: conrod_core::input::MouseButton::Left,
+                        xy,
+                        ..
+                    },
+                ) if *click_id == window_frame_id => {
+                    let pos = util::conrod_point_to_layout_pos(*xy, self.windowing_area_rect);
+                    self.windowing_state.win_tab_at(win_id, pos)
+                }
+                _ => None,
+            })
+            .last();
+        if let Some(tab_win_id) = clicked_tab.filter(|&tab_win_id| tab_win_id != win_id) {
+            self.windowing_state.set_active_tab(tab_win_id);
+            ui.needs_redraw();
+        }
+
+        let dblclick_count = ui
             .global_input()
             .events()
             .ui()
@@ -578,12 +1253,19 @@ impl<'a> WindowingContext<'a> {
             })
             .filter(|&x| x)
             .count() as u32;
+        let (collapse_dblclick_count, maximize_dblclick_count, title_bar_double_click_count) =
+            match builder.title_bar_double_click {
+                TitleBarDoubleClick::Collapse => (dblclick_count, 0, dblclick_count),
+                TitleBarDoubleClick::Maximize => (0, dblclick_count, dblclick_count),
+                TitleBarDoubleClick::Ignore => (0, 0, 0),
+                TitleBarDoubleClick::ReportOnly => (0, 0, dblclick_count),
+            };
         // Toggle the collapse state if the collapse button was pressed or the
         // title bar was double-clicked, but only if the caller has not
         // explicitly set the collapse state.
         if builder.is_collapsible
             && builder.is_collapsed.is_none()
-            && (event.collapse_clicked.0 as u32 + title_bar_double_click_count) % 2 == 1
+            && (event.collapse_clicked.0 as u32 + collapse_dblclick_count) % 2 == 1
         {
             self.windowing_state
                 .set_win_collapsed(win_id, !window_is_collapsed);
@@ -594,10 +1276,28 @@ impl<'a> WindowingContext<'a> {
             // else has changed in the rest of the UI during this update.
             ui.needs_redraw();
         }
+        // Likewise for the maximized state.
+        if builder.is_maximizable
+            && builder.is_maximized.is_none()
+            && (event.maximize_clicked.0 as u32 + maximize_dblclick_count) % 2 == 1
+        {
+            self.windowing_state
+                .set_win_maximized(win_id, !window_is_maximized);
+            ui.needs_redraw();
+        }
+        let close_requested =
+            builder.is_closable && builder.auto_close && event.close_clicked.0 > 0;
+        if close_requested {
+            self.windowing_state.request_win_close(win_id);
+        }
         let event = WindowEvent {
+            close_requested,
             collapse_clicked: event.collapse_clicked,
+            maximize_clicked: event.maximize_clicked,
             close_clicked: event.close_clicked,
             title_bar_double_click_count,
+            focus_gained,
+            focus_lost,
         };
         if window_is_collapsed {
             (event, None)
@@ -613,6 +1313,20 @@ impl<'a> WindowingContext<'a> {
     }
 }
 
+impl WindowEvent {
+    fn none() -> Self {
+        Self {
+            collapse_clicked: widget::button::TimesClicked(0),
+            maximize_clicked: widget::button::TimesClicked(0),
+            close_clicked: widget::button::TimesClicked(0),
+            title_bar_double_click_count: 0,
+            focus_gained: false,
+            focus_lost: false,
+            close_requested: false,
+        }
+    }
+}
+
 impl WindowSetter {
     pub fn set<W>(self, widget: W, ui: &mut UiCell) -> (widget::Id, W::Event)
     where
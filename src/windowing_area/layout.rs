@@ -1,8 +1,29 @@
 pub use dim::{Rect, RectF, RectI};
+pub use docking::DockSide;
+pub use events::WindowingEvent;
+pub use snapshot::LayoutSnapshot;
 
+use crate::util;
+
+use std::collections::HashMap;
+
+mod aspect_ratio;
+mod close;
 mod debug;
 mod dim;
+mod docking;
+mod events;
+mod focus;
+mod keyboard_adjust;
+mod modal;
+mod owner;
 mod snapping;
+mod snapshot;
+mod tab_group;
+mod tiling;
+
+/// The minimum client size used for windows which do not specify one.
+const DEFAULT_MIN_SIZE: [f32; 2] = [150.0, 50.0];
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum HitTest {
@@ -16,10 +37,27 @@ pub enum HitTest {
     TopRightCorner,
     BottomLeftCorner,
     BottomRightCorner,
+    /// The tab strip of a tab group, which sits below the title bar.
+    TabStrip,
     // CollapseButton,
     // CloseButton,
 }
 
+/// The z-order layer of a window. Windows in a higher layer are always kept
+/// above the windows in lower layers, and `bring_to_top` only raises a window
+/// to the top of its own layer.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum WindowLayer {
+    /// Kept below all other windows.
+    Background,
+    /// The layer of ordinary windows.
+    Normal,
+    /// Kept above ordinary windows, e.g. for palettes and tool windows.
+    Floating,
+    /// Kept above all other windows.
+    Overlay,
+}
+
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum WindowDragAction1D {
     None,
@@ -41,29 +79,131 @@ enum WindowPartY {
     BottomBorder,
 }
 
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
-pub struct WinId(pub(super) u32);
+/// Identifies a window in a `WindowingState`. Once the window has been
+/// destroyed, its `WinId` becomes stale and will not alias any window created
+/// later, even though its slot in the `WindowingState` may be reused.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct WinId {
+    index: u32,
+    generation: u32,
+}
 
 pub struct WindowingState {
     area_size: [f32; 2],
     hidpi_factor: f64,
     window_states: Vec<Option<WindowState>>,
     window_z_orders: Vec<u32>,
+    /// The windows from bottom to top, sorted by their layers.
     bottom_to_top_list: Vec<WinId>,
+    /// The window most recently brought to the top of its layer.
+    active_win: Option<WinId>,
+    /// The windows from the most recently brought to top to the least, which
+    /// is independent of the layers of the windows.
+    mru_list: Vec<WinId>,
+    /// The window which has the focus, which is independent of the z-order.
+    focused_win: Option<WinId>,
+    /// The events which have not been taken by `take_events` yet.
+    events: Vec<WindowingEvent>,
+    /// The windows closed by the last call to `apply_close_requests`.
+    closed_wins: Vec<WinId>,
+    /// The window whose title bar is flashing and when it started flashing.
+    flashing_win: Option<(WinId, std::time::Instant)>,
+    /// The generation of each slot, which is incremented when the window in
+    /// the slot is destroyed so that stale `WinId`s can be detected.
+    window_generations: Vec<u32>,
+    /// Indices of the slots that can be reused by `next_id`.
+    free_slots: Vec<u32>,
+    /// Whether each slot has been allocated by `next_id` and not destroyed
+    /// since, i.e. is not in `free_slots`.
+    window_slot_used: Vec<bool>,
+    /// The application-chosen keys of the windows, indexed by `WinId`.
+    window_keys: Vec<Option<String>>,
+    key_to_win_id: HashMap<String, WinId>,
+    /// The windows grouped together into tabs. Each group contains at least
+    /// two windows, of which only the active tab is displayed.
+    tab_groups: Vec<Vec<WinId>>,
+    /// The docked windows in the order they were docked. Windows docked
+    /// earlier take the outer positions.
+    docked_wins: Vec<WinId>,
+    /// The part of the windowing area not taken by docked windows.
+    work_area: RectF,
+    is_tiling: bool,
+    /// The split tree of the tiling mode. It is kept when the tiling mode is
+    /// disabled so that the tiles can be restored.
+    tile_tree: Option<tiling::TileNode>,
+    frame_metrics_config: FrameMetricsConfig,
     frame_metrics: FrameMetrics,
     maybe_dragging_window: Option<DraggingState>,
+    keyboard_adjusting: Option<keyboard_adjust::KeyboardAdjustState>,
     next_auto_position: [f32; 2],
 }
 
 struct WindowState {
+    title: String,
     rect: RectF,
     min_size: dim::SizeF,
+    /// The maximum client size of the window, if any.
+    max_size: Option<dim::SizeF>,
+    /// Whether the window can be resized by dragging its borders. Windows
+    /// which are not resizable can still be resized with
+    /// `set_win_normal_rect`.
+    is_resizable: bool,
+    /// The client aspect ratio (width / height) kept when the window is
+    /// resized by dragging.
+    aspect_ratio: Option<f32>,
+    /// Whether the window is prevented from being moved by dragging.
+    is_pinned: bool,
+    layer: WindowLayer,
+    /// Whether the window blocks the other windows while it is displayed.
+    /// Modal windows are kept above all other windows.
+    is_modal: bool,
+    /// The modal state last applied from the `WindowBuilder` by
+    /// `set_win_modal_from_builder`.
+    builder_is_modal: Option<bool>,
+    owner: Option<WinId>,
+    /// Whether the window is moved along when its owner is dragged.
+    follows_owner: bool,
+    /// Whether the window is hidden because its owner is not displayed or
+    /// is collapsed, as computed by `update_owner_hidden`.
+    is_owner_hidden: bool,
+    /// Whether the window was focused when the focus was last checked by
+    /// `win_take_focus_change`.
+    was_focused: bool,
+    /// The display rect of the window when the events were last taken by
+    /// `take_events`.
+    reported_rect: Option<RectI>,
+    is_close_requested: bool,
+    /// Whether the `CloseRequested` event of the pending close request has
+    /// been taken by `take_events`. The request is only applied after that,
+    /// so that the application has a chance to veto it.
+    is_close_request_reported: bool,
+    is_closed: bool,
     is_hidden: bool,
     is_collapsed: bool,
+    /// Whether the window fills the whole windowing area. The `rect` is kept
+    /// as the normal rect to restore to.
+    is_maximized: bool,
     /// This flag is used to keep track of whether the window is still being
     /// used. The method `sweep_unneeded` will remove all windows with this
     /// flag set to `false`.
     is_needed: bool,
+    /// Whether the window is in a tab group but is not the active tab. The
+    /// layout states of inactive tabs are stale and are only brought up to
+    /// date when the tab becomes active or is detached.
+    is_inactive_tab: bool,
+    docked_side: Option<DockSide>,
+    /// The docked side last applied from the `WindowBuilder` by
+    /// `set_win_docked_from_builder`, or `None` if none has been applied yet.
+    builder_docked_side: Option<Option<DockSide>>,
+    /// The width or height of the window when docked, depending on the side.
+    /// This is taken from the normal rect when the window is first docked.
+    dock_size: Option<f32>,
+    /// The rect of the window when docked, as computed by
+    /// `recompute_dock_layout`.
+    docked_rect: RectF,
+    /// The rect of the window when it is tiled in the tiling mode, as computed
+    /// by `update_tile_layout`.
+    tiled_rect: Option<RectF>,
     anchor_x: snapping::Anchor,
     anchor_y: snapping::Anchor,
 }
@@ -75,17 +215,60 @@ pub struct WindowInitialState {
     pub is_collapsed: bool,
 }
 
+/// The sizes of the window decorations in logical pixels, aligned to the
+/// physical pixel grid. They are made from the `FrameMetricsConfig` by the
+/// `FrameTheme` of the `WindowingArea`.
 #[derive(Clone, Copy, PartialEq, Debug)]
-pub(crate) struct FrameMetrics {
-    pub(crate) border_thickness: f64,
-    pub(crate) title_bar_height: f64,
-    pub(crate) gap_below_title_bar: f64,
+pub struct FrameMetrics {
+    /// The thickness of the frame around the window.
+    pub border_thickness: f64,
+    pub title_bar_height: f64,
+    /// The gap between the title bar and the tab strip or the client area.
+    pub gap_below_title_bar: f64,
     /// The window width of a collapsed window. This includes the borders on
     /// both sides.
-    pub(crate) collapsed_win_width: f64,
-    pub(crate) title_button_padding: f64,
-    pub(crate) title_button_width: f64,
-    pub(crate) title_text_padding: f64,
+    pub collapsed_win_width: f64,
+    /// The padding around the title bar buttons.
+    pub title_button_padding: f64,
+    pub title_button_width: f64,
+    /// The padding between the title text and the edges of the title bar or
+    /// the title bar buttons.
+    pub title_text_padding: f64,
+    pub tab_strip_height: f64,
+    pub tab_max_width: f64,
+    /// The distance within which the edges of a dragged window snap to the
+    /// edges of the work area and of other windows.
+    pub snap_threshold: f64,
+    /// The gap kept between snapped windows, and between the windows and the
+    /// edges of the work area they are snapped to.
+    pub snap_margin: f64,
+}
+
+/// The configured sizes of the window decorations in logical pixels, before
+/// they are aligned to the physical pixel grid.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct FrameMetricsConfig {
+    /// The thickness of the frame around the window.
+    pub border_thickness: f64,
+    pub title_bar_height: f64,
+    /// The gap between the title bar and the tab strip or the client area.
+    pub gap_below_title_bar: f64,
+    /// The width of a collapsed window, not including the borders.
+    pub collapsed_width: f64,
+    /// The padding around the title bar buttons.
+    pub title_button_padding: f64,
+    pub title_button_width: f64,
+    /// The padding between the title text and the edges of the title bar or
+    /// the title bar buttons.
+    pub title_text_padding: f64,
+    pub tab_strip_height: f64,
+    pub tab_max_width: f64,
+    /// The distance within which the edges of a dragged window snap to the
+    /// edges of the work area and of other windows.
+    pub snap_threshold: f64,
+    /// The gap kept between snapped windows, and between the windows and the
+    /// edges of the work area they are snapped to.
+    pub snap_margin: f64,
 }
 
 struct DraggingState {
@@ -96,15 +279,34 @@ struct DraggingState {
     last_snapped_x: Option<u32>,
     snap_candidates_y: Vec<(WinId, snapping::SnapSegmentH)>,
     last_snapped_y: Option<u32>,
+    /// The window whose tab group the dragged window will be merged into if
+    /// it is dropped at the current pointer position.
+    drop_target: Option<WinId>,
+}
+
+impl WindowState {
+    /// Whether the window is currently displayed, i.e. it is neither hidden,
+    /// closed, hidden by its owner nor an inactive tab.
+    fn is_displayed(&self) -> bool {
+        !self.is_hidden && !self.is_closed && !self.is_owner_hidden && !self.is_inactive_tab
+    }
+}
+
+impl WinId {
+    /// The index of the slot of the window in the `WindowingState`.
+    pub(crate) fn index(self) -> usize {
+        self.index as usize
+    }
 }
 
 impl HitTest {
     pub fn to_drag_action_1d<D: dim::Dir>(self) -> WindowDragAction1D {
         match <D as dim::Dir>::DIR {
             dim::Direction::Horizontal => match self {
-                HitTest::Content | HitTest::TopBorder | HitTest::BottomBorder => {
-                    WindowDragAction1D::None
-                }
+                HitTest::Content
+                | HitTest::TopBorder
+                | HitTest::BottomBorder
+                | HitTest::TabStrip => WindowDragAction1D::None,
                 HitTest::TitleBarOrDragArea => WindowDragAction1D::MoveWindow,
                 HitTest::LeftBorder | HitTest::TopLeftCorner | HitTest::BottomLeftCorner => {
                     WindowDragAction1D::ResizeLower
@@ -114,9 +316,10 @@ impl HitTest {
                 }
             },
             dim::Direction::Vertical => match self {
-                HitTest::Content | HitTest::LeftBorder | HitTest::RightBorder => {
-                    WindowDragAction1D::None
-                }
+                HitTest::Content
+                | HitTest::LeftBorder
+                | HitTest::RightBorder
+                | HitTest::TabStrip => WindowDragAction1D::None,
                 HitTest::TitleBarOrDragArea => WindowDragAction1D::MoveWindow,
                 HitTest::TopBorder | HitTest::TopLeftCorner | HitTest::TopRightCorner => {
                     WindowDragAction1D::ResizeLower
@@ -129,33 +332,67 @@ impl HitTest {
     }
 }
 
-impl FrameMetrics {
-    pub(crate) fn with_hidpi_factor(hidpi_factor: f64) -> Self {
-        let dpi_int = if hidpi_factor.fract() < 0.51 {
-            hidpi_factor.trunc()
-        } else {
-            hidpi_factor.trunc() + 1.0
-        };
-        let border_thickness = 4.0 * dpi_int / hidpi_factor;
-        let gap_below_title_bar = 1.0 * dpi_int / hidpi_factor;
-        let title_bar_height = (18.0 * hidpi_factor).round() / hidpi_factor;
-        let collapsed_win_width =
-            (150.0 * hidpi_factor + border_thickness * hidpi_factor * 2.0).round() / hidpi_factor;
-        let title_button_padding = (2.0 * hidpi_factor).round() / hidpi_factor;
-        let title_button_width = (16.0 * hidpi_factor).round() / hidpi_factor;
-        let title_text_padding = (4.0 * hidpi_factor).round() / hidpi_factor;
-        Self {
+impl FrameMetricsConfig {
+    /// Makes the metrics for the hidpi factor. The border and the gap below
+    /// the title bar are scaled by whole physical pixels so that they stay
+    /// sharp, and the other sizes are rounded to the physical pixel grid.
+    pub fn to_frame_metrics(&self, hidpi_factor: f64) -> FrameMetrics {
+        let dpi_int = util::hidpi_factor_int(hidpi_factor);
+        let snap_int = |x: f64| (x * dpi_int).round() / hidpi_factor;
+        let snap = |x: f64| (x * hidpi_factor).round() / hidpi_factor;
+        let border_thickness = snap_int(self.border_thickness);
+        let gap_below_title_bar = snap_int(self.gap_below_title_bar);
+        let collapsed_win_width = snap(self.collapsed_width + border_thickness * 2.0);
+        FrameMetrics {
             border_thickness,
-            title_bar_height,
+            title_bar_height: snap(self.title_bar_height),
             gap_below_title_bar,
             collapsed_win_width,
-            title_button_padding,
-            title_button_width,
-            title_text_padding,
+            title_button_padding: snap(self.title_button_padding),
+            title_button_width: snap(self.title_button_width),
+            title_text_padding: snap(self.title_text_padding),
+            tab_strip_height: snap(self.tab_strip_height),
+            tab_max_width: snap(self.tab_max_width),
+            snap_threshold: snap(self.snap_threshold),
+            snap_margin: snap(self.snap_margin),
         }
     }
 }
 
+impl Default for FrameMetricsConfig {
+    fn default() -> Self {
+        Self {
+            border_thickness: 4.0,
+            title_bar_height: 18.0,
+            gap_below_title_bar: 1.0,
+            collapsed_width: 150.0,
+            title_button_padding: 2.0,
+            title_button_width: 16.0,
+            title_text_padding: 4.0,
+            tab_strip_height: 18.0,
+            tab_max_width: 120.0,
+            snap_threshold: 12.0,
+            snap_margin: 8.0,
+        }
+    }
+}
+
+impl FrameMetrics {
+    /// Makes the default metrics of the window decorations, aligned to the
+    /// physical pixel grid of the hidpi factor.
+    pub fn with_hidpi_factor(hidpi_factor: f64) -> Self {
+        FrameMetricsConfig::default().to_frame_metrics(hidpi_factor)
+    }
+
+    /// Gets the width of each tab in a tab strip of `tab_count` tabs, aligned
+    /// to the physical pixel grid.
+    pub(crate) fn tab_width(&self, strip_width: f64, tab_count: usize, hidpi_factor: f64) -> f64 {
+        let tab_width =
+            (strip_width / tab_count.max(1) as f64 * hidpi_factor).floor() / hidpi_factor;
+        tab_width.min(self.tab_max_width)
+    }
+}
+
 impl WindowingState {
     pub fn new() -> Self {
         Self {
@@ -164,42 +401,87 @@ impl WindowingState {
             window_states: Vec::new(),
             window_z_orders: Vec::new(),
             bottom_to_top_list: Vec::new(),
+            active_win: None,
+            mru_list: Vec::new(),
+            focused_win: None,
+            events: Vec::new(),
+            closed_wins: Vec::new(),
+            flashing_win: None,
+            window_generations: Vec::new(),
+            free_slots: Vec::new(),
+            window_slot_used: Vec::new(),
+            window_keys: Vec::new(),
+            key_to_win_id: HashMap::new(),
+            tab_groups: Vec::new(),
+            docked_wins: Vec::new(),
+            work_area: RectF {
+                x: 0.0,
+                y: 0.0,
+                w: 16_777_216.0,
+                h: 16_777_216.0,
+            },
+            is_tiling: false,
+            tile_tree: None,
+            frame_metrics_config: FrameMetricsConfig::default(),
             frame_metrics: FrameMetrics::with_hidpi_factor(1.0),
             maybe_dragging_window: None,
+            keyboard_adjusting: None,
             next_auto_position: [32.0, 32.0],
         }
     }
 
+    /// Sets the size of the windowing area and the hidpi factor for the tests,
+    /// which have no `WindowingArea`. If the hidpi factor changes, the frame
+    /// metrics are made from the `FrameMetricsConfig` the same as by the
+    /// default `FrameTheme`.
+    #[cfg(test)]
     pub(crate) fn set_dimensions(&mut self, area_size: [f32; 2], hidpi_factor: f64) {
-        let mut has_changed = false;
-        if self.area_size != area_size {
-            has_changed = true;
-        }
-        if self.hidpi_factor != hidpi_factor {
-            self.frame_metrics = FrameMetrics::with_hidpi_factor(hidpi_factor);
-            has_changed = true;
+        let frame_metrics = if self.hidpi_factor != hidpi_factor {
+            self.frame_metrics_config.to_frame_metrics(hidpi_factor)
+        } else {
+            self.frame_metrics
+        };
+        let config = self.frame_metrics_config;
+        self.set_dimensions_and_frame_metrics(area_size, hidpi_factor, config, frame_metrics);
+    }
+
+    /// Sets the size of the windowing area, the hidpi factor and the frame
+    /// metrics which the `FrameTheme` of the `WindowingArea` has made from
+    /// `config`, then updates the layout once if anything has changed.
+    pub(crate) fn set_dimensions_and_frame_metrics(
+        &mut self,
+        area_size: [f32; 2],
+        hidpi_factor: f64,
+        config: FrameMetricsConfig,
+        frame_metrics: FrameMetrics,
+    ) {
+        self.frame_metrics_config = config;
+        if self.area_size == area_size
+            && self.hidpi_factor == hidpi_factor
+            && self.frame_metrics == frame_metrics
+        {
+            return;
         }
         self.area_size = area_size;
         self.hidpi_factor = hidpi_factor;
-        if has_changed {
-            self.recompute_snapped_win_rects();
-        }
+        self.frame_metrics = frame_metrics;
+        self.update_work_area();
     }
 
     fn recompute_snapped_win_rects(&mut self) {
         for i in 0..self.window_states.len() {
-            let win_id = WinId(i as u32);
-            self.win_recompute_snapping_rect(win_id);
+            if let Some(win_id) = self.win_id_at(i) {
+                self.win_recompute_snapping_rect(win_id);
+            }
         }
     }
 
     fn win_recompute_snapping_rect(&mut self, win_id: WinId) {
-        let win_idx = win_id.0 as usize;
-        let win = match &self.window_states[win_idx] {
+        let win = match self.win_state(win_id) {
             Some(win) => win,
             None => return,
         };
-        if win.is_hidden {
+        if win.is_hidden || win.docked_side.is_some() {
             return;
         }
         if win.anchor_x == snapping::Anchor::None && win.anchor_y == snapping::Anchor::None {
@@ -207,47 +489,52 @@ impl WindowingState {
         }
 
         let hidpi_factor = self.hidpi_factor as f32;
-        let border_thickness = self.frame_metrics.border_thickness as f32;
-        let title_bar_height = self.frame_metrics.title_bar_height as f32;
-        let area_w = (self.area_size[0] * hidpi_factor) as i32;
-        let area_h = (self.area_size[1] * hidpi_factor) as i32;
-        let snap_margin = (8.0 * hidpi_factor).round() as i32;
+        let work_area = self.work_area_int();
+        let snap_margin = (self.frame_metrics.snap_margin as f32 * hidpi_factor).round() as i32;
 
         let mut rect = self
             .win_normal_rect_int(win_id)
             .unwrap_or_else(|| unreachable!());
-        let display_size = self
-            .win_display_rect_int(win_id)
-            .unwrap_or_else(|| unreachable!())
-            .size();
-        let min_w = ((border_thickness * 2.0 + win.min_size.w) * hidpi_factor).round() as i32;
-        let min_h = ((border_thickness * 2.0 + title_bar_height + win.min_size.h) * hidpi_factor)
-            .round() as i32;
+        let display_size = self.win_normal_display_size_int(win, rect);
+        let (min_size, max_size) = self.win_size_limits_int(win_id);
+        let is_resizable = win.is_resizable;
 
+        let area_r = work_area.x + work_area.w;
+        let area_b = work_area.y + work_area.h;
         match win.anchor_x {
             snapping::Anchor::None => {}
             snapping::Anchor::LowerEdge => {
-                rect.x = 0 + snap_margin;
+                rect.x = work_area.x + snap_margin;
             }
             snapping::Anchor::UpperEdge => {
-                rect.x = area_w - display_size.w - snap_margin;
+                rect.x = area_r - display_size.w - snap_margin;
             }
             snapping::Anchor::LowerAndUpperEdges => {
-                rect.x = 0 + snap_margin;
-                rect.w = min_w.max(area_w - rect.x - snap_margin);
+                rect.x = work_area.x + snap_margin;
+                if is_resizable {
+                    rect.w = min_size
+                        .w
+                        .max(area_r - rect.x - snap_margin)
+                        .min(max_size.w);
+                }
             }
         }
         match win.anchor_y {
             snapping::Anchor::None => {}
             snapping::Anchor::LowerEdge => {
-                rect.y = 0 + snap_margin;
+                rect.y = work_area.y + snap_margin;
             }
             snapping::Anchor::UpperEdge => {
-                rect.y = area_h - display_size.h - snap_margin;
+                rect.y = area_b - display_size.h - snap_margin;
             }
             snapping::Anchor::LowerAndUpperEdges => {
-                rect.y = 0 + snap_margin;
-                rect.h = min_h.max(area_h - rect.y - snap_margin);
+                rect.y = work_area.y + snap_margin;
+                if is_resizable {
+                    rect.h = min_size
+                        .h
+                        .max(area_b - rect.y - snap_margin)
+                        .min(max_size.h);
+                }
             }
         }
         self.set_win_normal_rect_int(win_id, rect);
@@ -257,16 +544,19 @@ impl WindowingState {
         let border_thickness = self.frame_metrics.border_thickness as f32;
         let title_bar_height = self.frame_metrics.title_bar_height as f32;
         let collapsed_win_width = self.frame_metrics.collapsed_win_width as f32;
+        let work_area = self.work_area;
 
         for &mut WindowState {
             rect: ref mut window_rect,
             min_size,
             is_hidden,
             is_collapsed,
+            docked_side,
+            tiled_rect,
             ..
         } in self.window_states.iter_mut().filter_map(|x| x.as_mut())
         {
-            if is_hidden {
+            if is_hidden || docked_side.is_some() || tiled_rect.is_some() {
                 continue;
             }
             let width_to_test = if is_collapsed {
@@ -279,50 +569,149 @@ impl WindowingState {
             } else {
                 window_rect.w
             };
-            if window_rect.x <= width_to_test - display_width - border_thickness {
-                window_rect.x = width_to_test - display_width - border_thickness;
-            } else if window_rect.x > self.area_size[0] - width_to_test {
-                window_rect.x = self.area_size[0] - width_to_test;
+            let min_x = work_area.x + width_to_test - display_width - border_thickness;
+            let max_x = work_area.x + work_area.w - width_to_test;
+            let min_y = work_area.y - border_thickness;
+            let max_y = work_area.y + work_area.h - (border_thickness + title_bar_height);
+            if window_rect.x <= min_x {
+                window_rect.x = min_x;
+            } else if window_rect.x > max_x {
+                window_rect.x = max_x;
             }
-            if window_rect.y <= -border_thickness {
-                window_rect.y = -border_thickness;
-            } else if window_rect.y > self.area_size[1] - (border_thickness + title_bar_height) {
-                window_rect.y = self.area_size[1] - (border_thickness + title_bar_height);
+            if window_rect.y <= min_y {
+                window_rect.y = min_y;
+            } else if window_rect.y > max_y {
+                window_rect.y = max_y;
             }
         }
     }
 
+    /// Allocates a `WinId` for a new window. The slots of destroyed windows
+    /// are reused, but the returned `WinId` will never be equal to the stale
+    /// `WinId` of a destroyed window.
     pub fn next_id(&mut self) -> WinId {
-        let id = self.window_states.len() as u32;
+        if let Some(index) = self.free_slots.pop() {
+            self.window_slot_used[index as usize] = true;
+            let win_id = WinId {
+                index,
+                generation: self.window_generations[index as usize],
+            };
+            let z_order = self.window_z_orders[index as usize];
+            self.bottom_to_top_list[z_order as usize] = win_id;
+            return win_id;
+        }
+        let index = self.window_states.len() as u32;
         self.window_states.push(None);
-        self.window_z_orders.push(id);
-        let win_id = WinId(id);
+        self.window_z_orders.push(index);
+        self.window_generations.push(0);
+        self.window_slot_used.push(true);
+        self.window_keys.push(None);
+        let win_id = WinId {
+            index,
+            generation: 0,
+        };
         self.bottom_to_top_list.push(win_id);
         win_id
     }
 
+    /// Checks whether `win_id` still refers to a window slot that has not been
+    /// destroyed. Note that a valid `WinId` may refer to a window which has
+    /// not been initialized yet.
+    pub fn win_id_is_valid(&self, win_id: WinId) -> bool {
+        self.window_generations.get(win_id.index()) == Some(&win_id.generation)
+    }
+
+    /// Gets the `WinId` of the slot at `index` if the slot is in use.
+    pub(crate) fn win_id_at(&self, index: usize) -> Option<WinId> {
+        let generation = *self.window_generations.get(index)?;
+        if !self.window_slot_used[index] {
+            return None;
+        }
+        Some(WinId {
+            index: index as u32,
+            generation,
+        })
+    }
+
+    fn win_state(&self, win_id: WinId) -> Option<&WindowState> {
+        if !self.win_id_is_valid(win_id) {
+            return None;
+        }
+        self.window_states[win_id.index()].as_ref()
+    }
+
+    fn win_state_mut(&mut self, win_id: WinId) -> Option<&mut WindowState> {
+        if !self.win_id_is_valid(win_id) {
+            return None;
+        }
+        self.window_states[win_id.index()].as_mut()
+    }
+
+    /// Destroys the window in the slot at `index` and makes the slot
+    /// available for reuse.
+    fn free_slot(&mut self, index: usize) {
+        if let Some(win_id) = self.win_id_at(index) {
+            self.remove_from_tab_group(win_id);
+            self.set_win_docked(win_id, None);
+            self.mru_list.retain(|&x| x != win_id);
+        }
+        self.window_states[index] = None;
+        self.window_generations[index] = self.window_generations[index].wrapping_add(1);
+        if let Some(key) = self.window_keys[index].take() {
+            self.key_to_win_id.remove(&key);
+        }
+        self.free_slots.push(index as u32);
+        self.window_slot_used[index] = false;
+        self.update_tile_layout();
+        self.update_focus_displayed();
+    }
+
+    /// Gets the `WinId` of the window identified by the application-chosen
+    /// `key`, allocating a new `WinId` if the key has not been seen before.
+    /// Unlike `WinId`s obtained from `next_id`, keyed windows can be matched
+    /// up with a `LayoutSnapshot` taken in a previous session regardless of
+    /// the order in which the windows are created.
+    pub fn id_for_key(&mut self, key: &str) -> WinId {
+        if let Some(&win_id) = self.key_to_win_id.get(key) {
+            return win_id;
+        }
+        let win_id = self.next_id();
+        self.window_keys[win_id.index()] = Some(key.to_owned());
+        self.key_to_win_id.insert(key.to_owned(), win_id);
+        win_id
+    }
+
+    /// Looks up the `WinId` of the window identified by `key` without
+    /// allocating a new one.
+    pub fn win_id_by_key(&self, key: &str) -> Option<WinId> {
+        self.key_to_win_id.get(key).copied()
+    }
+
+    /// Gets the key of a window allocated with `id_for_key`.
+    pub fn win_key(&self, win_id: WinId) -> Option<&str> {
+        if !self.win_id_is_valid(win_id) {
+            return None;
+        }
+        self.window_keys[win_id.index()].as_deref()
+    }
+
     /// Ensures that the window specified by `win_id` has been initialized. If
     /// the window has not been initialized, the `init` callback is called to
-    /// obtain the initial states for the window.
+    /// obtain the initial states for the window. Stale `WinId`s are ignored.
     pub fn ensure_init<F>(&mut self, win_id: WinId, init: F)
     where
         F: FnOnce() -> WindowInitialState,
     {
-        let WinId(win_idx) = win_id;
-        let win = &mut self.window_states[win_idx as usize];
+        if !self.win_id_is_valid(win_id) {
+            return;
+        }
+        let frame_size = self.win_frame_size(win_id);
+        let win = &mut self.window_states[win_id.index()];
         if win.is_none() {
-            let double_border = self.frame_metrics.border_thickness as f32 * 2.0;
-            let additional_height = self.frame_metrics.title_bar_height as f32
-                + self.frame_metrics.gap_below_title_bar as f32;
-
             let initial_state = init();
-            let min_size: dim::SizeF = initial_state
-                .min_size
-                .unwrap_or_else(|| [150.0, 50.0])
-                .into();
-            let w = initial_state.client_size[0].max(min_size.w) + double_border;
-            let h =
-                initial_state.client_size[1].max(min_size.h) + double_border + additional_height;
+            let min_size: dim::SizeF = initial_state.min_size.unwrap_or(DEFAULT_MIN_SIZE).into();
+            let w = initial_state.client_size[0].max(min_size.w) + frame_size.w;
+            let h = initial_state.client_size[1].max(min_size.h) + frame_size.h;
             let next_auto_pos = &mut self.next_auto_position;
             let area_h = self.area_size[1];
             let [x, y] = initial_state.position.unwrap_or_else(|| {
@@ -336,29 +725,70 @@ impl WindowingState {
             });
             let rect = RectF { x, y, w, h };
             *win = Some(WindowState {
+                title: String::new(),
                 rect,
                 min_size,
+                max_size: None,
+                is_resizable: true,
+                aspect_ratio: None,
+                is_pinned: false,
+                layer: WindowLayer::Normal,
+                is_modal: false,
+                builder_is_modal: None,
+                owner: None,
+                follows_owner: false,
+                is_owner_hidden: false,
+                was_focused: false,
+                reported_rect: None,
+                is_close_requested: false,
+                is_close_request_reported: false,
+                is_closed: false,
                 is_hidden: false,
                 is_collapsed: initial_state.is_collapsed,
+                is_maximized: false,
                 is_needed: true,
+                is_inactive_tab: false,
+                docked_side: None,
+                builder_docked_side: None,
+                dock_size: None,
+                docked_rect: rect,
+                tiled_rect: None,
                 anchor_x: snapping::Anchor::None,
                 anchor_y: snapping::Anchor::None,
             });
-            self.bring_to_top(win_id);
+            self.focus_and_bring_to_top(win_id);
+            self.update_tile_layout();
         }
     }
 
-    pub(crate) fn frame_metrics(&self) -> FrameMetrics {
+    pub fn frame_metrics(&self) -> FrameMetrics {
         self.frame_metrics
     }
 
+    pub fn frame_metrics_config(&self) -> FrameMetricsConfig {
+        self.frame_metrics_config
+    }
+
+    /// Sets the sizes of the window decorations. The window rects, hit testing
+    /// and the work area follow the new sizes. Note that the `FrameTheme` of
+    /// the `WindowingArea` makes the final metrics from this config on its
+    /// next update.
+    pub fn set_frame_metrics_config(&mut self, config: FrameMetricsConfig) {
+        if self.frame_metrics_config != config {
+            let frame_metrics = config.to_frame_metrics(self.hidpi_factor);
+            let area_size = self.area_size;
+            let hidpi_factor = self.hidpi_factor;
+            self.set_dimensions_and_frame_metrics(area_size, hidpi_factor, config, frame_metrics);
+        }
+    }
+
+    /// Gets the number of window slots, including those not in use.
     pub fn win_count(&self) -> usize {
         self.window_states.len()
     }
 
     pub(crate) fn set_needed(&mut self, win_id: WinId, is_needed: bool) {
-        let WinId(win_idx) = win_id;
-        if let Some(win) = &mut self.window_states[win_idx as usize] {
+        if let Some(win) = self.win_state_mut(win_id) {
             win.is_needed = is_needed;
         }
     }
@@ -370,9 +800,13 @@ impl WindowingState {
     }
 
     pub(crate) fn sweep_unneeded(&mut self) {
-        for win in &mut self.window_states {
-            if win.as_ref().map_or(false, |x| !x.is_needed) {
-                *win = None;
+        self.mark_owned_unneeded();
+        for i in 0..self.window_states.len() {
+            if self.window_states[i].as_ref().is_some_and(|x| !x.is_needed) {
+                if let Some(win_id) = self.win_id_at(i) {
+                    self.push_event(WindowingEvent::Swept(win_id));
+                }
+                self.free_slot(i);
             }
         }
     }
@@ -399,26 +833,62 @@ impl WindowingState {
     }
 
     pub fn specific_win_hit_test(&self, win_id: WinId, pos: [f32; 2]) -> Option<HitTest> {
-        let WinId(win_idx) = win_id;
-        let win = self.window_states[win_idx as usize].as_ref()?;
-        if win.is_hidden {
+        let win = self.win_state(win_id)?;
+        if !win.is_displayed() {
             return None;
         }
-        let is_collapsed = win.is_collapsed;
-        let win_rect = if is_collapsed {
-            self.win_display_rect(win_id)?
-        } else {
-            win.rect
-        };
+        let win_rect = self.win_display_rect_unaligned(win);
         let x = pos[0] - win_rect.x;
         let y = pos[1] - win_rect.y;
         let w = win_rect.w;
         let h = win_rect.h;
-        window_hit_test([w, h], [x, y], self.hidpi_factor as f32, self.frame_metrics)
+        // Maximized windows cannot be resized.
+        let is_resizable = win.is_resizable && !win.is_maximized;
+        let has_tab_strip = self.win_has_tab_strip(win_id);
+        let ht = window_hit_test(
+            [w, h],
+            [x, y],
+            self.hidpi_factor as f32,
+            self.frame_metrics,
+            is_resizable,
+            has_tab_strip,
+        )?;
+        match (win.docked_side, win.tiled_rect) {
+            (Some(side), _) => Some(side.map_hit_test(ht)),
+            (None, Some(tiled_rect)) if !win.is_maximized => {
+                Some(self.tiled_hit_test(tiled_rect, ht))
+            }
+            _ => Some(ht),
+        }
     }
 
+    /// Gets the window at the top of the z-order, which is in the highest
+    /// layer that has windows.
     pub fn topmost_win(&self) -> Option<WinId> {
-        self.bottom_to_top_list.last().copied()
+        self.bottom_to_top_list
+            .iter()
+            .rev()
+            .copied()
+            .find(|&win_id| self.win_id_is_valid(win_id))
+    }
+
+    /// Gets the window most recently brought to the top of its layer, which
+    /// is the window that the user is interacting with. This is the same as
+    /// `topmost_win` unless there are windows in higher layers.
+    pub fn active_win(&self) -> Option<WinId> {
+        self.active_win
+            .filter(|&win_id| self.win_state(win_id).is_some())
+            .or_else(|| self.topmost_win())
+    }
+
+    /// Gets the displayed windows in most-recently-used order, starting from
+    /// the window most recently brought to the top. Unlike the z-order, this
+    /// order is not affected by the layers of the windows.
+    pub fn mru_wins(&self) -> impl Iterator<Item = WinId> + '_ {
+        self.mru_list
+            .iter()
+            .copied()
+            .filter(move |&win_id| self.win_state(win_id).is_some_and(|win| win.is_displayed()))
     }
 
     /// Retrieves the `Rect` of a window in its normal state. The `Rect` is
@@ -426,8 +896,7 @@ impl WindowingState {
     /// returned `Rect` contains f32 dimensions, it may not suitable for use
     /// with GUI toolkits that use f64 internally due to the limited precision.
     pub fn win_normal_rect(&self, win_id: WinId) -> Option<RectF> {
-        let WinId(win_idx) = win_id;
-        let win = self.window_states[win_idx as usize].as_ref()?;
+        let win = self.win_state(win_id)?;
         let rect = win.rect;
         let hidpi_factor = self.hidpi_factor as f32;
         Some(RectF {
@@ -441,8 +910,7 @@ impl WindowingState {
     /// Retrieves the `RectInt` of a window in its normal state. The `RectInt`
     /// is in unscaled physical pixels.
     pub fn win_normal_rect_int(&self, win_id: WinId) -> Option<RectI> {
-        let WinId(win_idx) = win_id;
-        let win = self.window_states[win_idx as usize].as_ref()?;
+        let win = self.win_state(win_id)?;
         let rect = win.rect;
         let hidpi_factor = self.hidpi_factor as f32;
         Some(RectI {
@@ -458,8 +926,7 @@ impl WindowingState {
     /// calculations use f64 so that the results are precise enough for GUI
     /// toolkits that use f64 internally.
     pub fn win_normal_rect_f64(&self, win_id: WinId) -> Option<[f64; 4]> {
-        let WinId(win_idx) = win_id;
-        let win = self.window_states[win_idx as usize].as_ref()?;
+        let win = self.win_state(win_id)?;
         let rect = win.rect;
         let hidpi_factor = self.hidpi_factor;
         Some([
@@ -470,56 +937,187 @@ impl WindowingState {
         ])
     }
 
+    /// Gets the logical size of a collapsed window.
+    fn collapsed_win_size(&self) -> dim::SizeF {
+        let border_thickness = self.frame_metrics.border_thickness as f32;
+        let title_bar_height = self.frame_metrics.title_bar_height as f32;
+        let collapsed_win_width = self.frame_metrics.collapsed_win_width as f32;
+        dim::SizeF {
+            w: collapsed_win_width,
+            h: title_bar_height + border_thickness * 2.0,
+        }
+    }
+
+    /// Gets the logical size taken by the window frame around the client
+    /// area of the window, which is added to the client size limits.
+    fn win_frame_size(&self, win_id: WinId) -> dim::SizeF {
+        let border_thickness = self.frame_metrics.border_thickness as f32;
+        let mut frame_h = border_thickness * 2.0
+            + self.frame_metrics.title_bar_height as f32
+            + self.frame_metrics.gap_below_title_bar as f32;
+        if self.win_has_tab_strip(win_id) {
+            frame_h += self.frame_metrics.tab_strip_height as f32;
+        }
+        dim::SizeF {
+            w: border_thickness * 2.0,
+            h: frame_h,
+        }
+    }
+
+    /// Gets the logical minimum and maximum sizes of a window, including the
+    /// window frame. The maximum size is infinite if the window has none.
+    fn win_size_limits(&self, win_id: WinId) -> (dim::SizeF, dim::SizeF) {
+        let win = match self.win_state(win_id) {
+            Some(win) => win,
+            None => {
+                let unlimited = dim::SizeF {
+                    w: f32::INFINITY,
+                    h: f32::INFINITY,
+                };
+                return (dim::SizeF { w: 0.0, h: 0.0 }, unlimited);
+            }
+        };
+        let frame_size = self.win_frame_size(win_id);
+        let min_size = dim::SizeF {
+            w: frame_size.w + win.min_size.w,
+            h: frame_size.h + win.min_size.h,
+        };
+        let max_size = match win.max_size {
+            Some(max_size) => dim::SizeF {
+                w: frame_size.w + max_size.w.max(win.min_size.w),
+                h: frame_size.h + max_size.h.max(win.min_size.h),
+            },
+            None => dim::SizeF {
+                w: f32::INFINITY,
+                h: f32::INFINITY,
+            },
+        };
+        (min_size, max_size)
+    }
+
+    /// Gets the minimum and maximum sizes of a window in physical pixels,
+    /// including the window frame.
+    fn win_size_limits_int(&self, win_id: WinId) -> (dim::SizeI, dim::SizeI) {
+        let hidpi_factor = self.hidpi_factor as f32;
+        let (min_size, max_size) = self.win_size_limits(win_id);
+        // Infinite sizes saturate to `i32::MAX`.
+        let to_int = |size: dim::SizeF| dim::SizeI {
+            w: (size.w * hidpi_factor).round() as i32,
+            h: (size.h * hidpi_factor).round() as i32,
+        };
+        (to_int(min_size), to_int(max_size))
+    }
+
+    /// Limits the outer size of a window in physical pixels to the size
+    /// limits of the window. If the aspect ratio of the window is locked, the
+    /// height is adjusted to follow the width.
+    fn constrain_win_size_int(&self, win_id: WinId, size: dim::SizeI) -> dim::SizeI {
+        let (min_size, max_size) = self.win_size_limits_int(win_id);
+        match self.win_aspect_ratio(win_id) {
+            Some(aspect_ratio) => {
+                let frame_size = self.win_frame_size_int(win_id);
+                let (min_size, max_size) = aspect_ratio::limit_sizes_to_aspect_ratio(
+                    min_size,
+                    max_size,
+                    frame_size,
+                    aspect_ratio,
+                );
+                let w = size.w.max(min_size.w).min(max_size.w);
+                dim::SizeI {
+                    w,
+                    h: aspect_ratio::follow_aspect_ratio::<dim::Horizontal>(
+                        w,
+                        frame_size,
+                        aspect_ratio,
+                    ),
+                }
+            }
+            None => dim::SizeI {
+                w: size.w.max(min_size.w).min(max_size.w),
+                h: size.h.max(min_size.h).min(max_size.h),
+            },
+        }
+    }
+
+    /// Gets the display size of a window with the normal rect `rect` in
+    /// physical pixels, as if the window is not maximized or tiled. This is
+    /// the size that the anchors of the window apply to.
+    fn win_normal_display_size_int(&self, win: &WindowState, rect: RectI) -> dim::SizeI {
+        if win.is_collapsed {
+            let hidpi_factor = self.hidpi_factor as f32;
+            let collapsed_size = self.collapsed_win_size();
+            dim::SizeI {
+                w: (collapsed_size.w * hidpi_factor).round() as i32,
+                h: (collapsed_size.h * hidpi_factor).round() as i32,
+            }
+        } else {
+            rect.size()
+        }
+    }
+
+    /// Gets the rect that a window is made to occupy in place of its normal
+    /// rect, such as when it is docked, maximized or tiled.
+    fn win_arranged_rect(&self, win: &WindowState) -> Option<RectF> {
+        if win.docked_side.is_some() {
+            Some(win.docked_rect)
+        } else if win.is_maximized {
+            Some(self.work_area)
+        } else {
+            win.tiled_rect
+        }
+    }
+
+    /// Gets the display rect of a window before aligning to the physical
+    /// pixel grid.
+    fn win_display_rect_unaligned(&self, win: &WindowState) -> RectF {
+        let rect = self.win_arranged_rect(win).unwrap_or(win.rect);
+        if win.is_collapsed {
+            let collapsed_size = self.collapsed_win_size();
+            RectF {
+                x: rect.x,
+                y: rect.y,
+                w: collapsed_size.w,
+                h: collapsed_size.h,
+            }
+        } else {
+            rect
+        }
+    }
+
     /// Retrieves the `Rect` of a window for display. The `Rect` is adjusted to
     /// align to the physical pixel grid. Note that since the returned `Rect`
     /// contains f32 dimensions, it may not suitable for use with GUI toolkits
     /// that use f64 internally due to the limited precision.
     pub fn win_display_rect(&self, win_id: WinId) -> Option<RectF> {
-        let WinId(win_idx) = win_id;
-        let win = self.window_states[win_idx as usize].as_ref()?;
-        if win.is_hidden {
+        let win = self.win_state(win_id)?;
+        if !win.is_displayed() {
             return None;
         }
-        if win.is_collapsed {
-            let rect = win.rect;
-            let hidpi_factor = self.hidpi_factor as f32;
-            let border_thickness = self.frame_metrics.border_thickness as f32;
-            let title_bar_height = self.frame_metrics.title_bar_height as f32;
-            let collapsed_win_width = self.frame_metrics.collapsed_win_width as f32;
-            Some(RectF {
-                x: (rect.x * hidpi_factor).round() / hidpi_factor,
-                y: (rect.y * hidpi_factor).round() / hidpi_factor,
-                w: collapsed_win_width,
-                h: title_bar_height + border_thickness * 2.0,
-            })
-        } else {
-            self.win_normal_rect(win_id)
-        }
+        let rect = self.win_display_rect_unaligned(win);
+        let hidpi_factor = self.hidpi_factor as f32;
+        Some(RectF {
+            x: (rect.x * hidpi_factor).round() / hidpi_factor,
+            y: (rect.y * hidpi_factor).round() / hidpi_factor,
+            w: (rect.w * hidpi_factor).round() / hidpi_factor,
+            h: (rect.h * hidpi_factor).round() / hidpi_factor,
+        })
     }
 
     /// Retrieves the `RectInt` of a window for display. The `RectInt` is in
     /// unscaled physical pixels.
     pub fn win_display_rect_int(&self, win_id: WinId) -> Option<RectI> {
-        let WinId(win_idx) = win_id;
-        let win = self.window_states[win_idx as usize].as_ref()?;
-        if win.is_hidden {
+        let win = self.win_state(win_id)?;
+        if !win.is_displayed() {
             return None;
         }
-        if win.is_collapsed {
-            let rect = win.rect;
-            let hidpi_factor = self.hidpi_factor as f32;
-            let border_thickness = self.frame_metrics.border_thickness as f32;
-            let title_bar_height = self.frame_metrics.title_bar_height as f32;
-            let collapsed_win_width = self.frame_metrics.collapsed_win_width as f32;
-            Some(RectI {
-                x: (rect.x * hidpi_factor).round() as i32,
-                y: (rect.y * hidpi_factor).round() as i32,
-                w: (collapsed_win_width * hidpi_factor).round() as i32,
-                h: ((title_bar_height + border_thickness * 2.0) * hidpi_factor).round() as i32,
-            })
-        } else {
-            self.win_normal_rect_int(win_id)
-        }
+        let rect = self.win_display_rect_unaligned(win);
+        let hidpi_factor = self.hidpi_factor as f32;
+        Some(RectI {
+            x: (rect.x * hidpi_factor).round() as i32,
+            y: (rect.y * hidpi_factor).round() as i32,
+            w: (rect.w * hidpi_factor).round() as i32,
+            h: (rect.h * hidpi_factor).round() as i32,
+        })
     }
 
     /// Retrieves the x, y, width and height of a window for display. The
@@ -527,39 +1125,80 @@ impl WindowingState {
     /// calculations use f64 so that the results are precise enough for GUI
     /// toolkits that use f64 internally.
     pub fn win_display_rect_f64(&self, win_id: WinId) -> Option<[f64; 4]> {
-        let WinId(win_idx) = win_id;
-        let win = self.window_states[win_idx as usize].as_ref()?;
-        if win.is_hidden {
+        let win = self.win_state(win_id)?;
+        if !win.is_displayed() {
             return None;
         }
-        if win.is_collapsed {
-            let rect = win.rect;
-            let hidpi_factor = self.hidpi_factor;
-            let border_thickness = self.frame_metrics.border_thickness;
-            let title_bar_height = self.frame_metrics.title_bar_height;
-            let collapsed_win_width = self.frame_metrics.collapsed_win_width;
-            Some([
-                (rect.x as f64 * hidpi_factor).round() / hidpi_factor,
-                (rect.y as f64 * hidpi_factor).round() / hidpi_factor,
-                collapsed_win_width,
-                title_bar_height + border_thickness * 2.0,
-            ])
-        } else {
-            self.win_normal_rect_f64(win_id)
+        let rect = self.win_display_rect_unaligned(win);
+        let hidpi_factor = self.hidpi_factor;
+        Some([
+            (rect.x as f64 * hidpi_factor).round() / hidpi_factor,
+            (rect.y as f64 * hidpi_factor).round() / hidpi_factor,
+            (rect.w as f64 * hidpi_factor).round() / hidpi_factor,
+            (rect.h as f64 * hidpi_factor).round() / hidpi_factor,
+        ])
+    }
+
+    /// Moves and resizes a window by setting its normal rect in logical
+    /// units. The size includes the window frame and is limited to the
+    /// minimum and maximum sizes of the window, the height is adjusted to keep
+    /// the aspect ratio of the window if it is locked, and the rect is rounded
+    /// to the physical pixel grid. The window is anchored to the edges of the
+    /// work area that it is snapped to, the same as if it had been dragged
+    /// there. Any ongoing drag of the window is aborted. A maximized window is
+    /// restored and a docked window is undocked, so that the new rect is
+    /// shown, but a tiled window only shows it once the tiling mode is
+    /// disabled. A window docked by `WindowBuilder::dock` stays undocked
+    /// until the builder docks it to another side.
+    pub fn set_win_normal_rect(&mut self, win_id: WinId, rect: RectF) {
+        if self.win_state(win_id).is_none() {
+            return;
         }
+        if self.current_dragging_win().map(|(id, _)| id) == Some(win_id) {
+            self.win_drag_end(true);
+        }
+        self.set_win_maximized(win_id, false);
+        self.set_win_docked(win_id, None);
+        let hidpi_factor = self.hidpi_factor as f32;
+        let size = self.constrain_win_size_int(
+            win_id,
+            dim::SizeI {
+                w: (rect.w * hidpi_factor).round() as i32,
+                h: (rect.h * hidpi_factor).round() as i32,
+            },
+        );
+        let rect = RectI {
+            x: (rect.x * hidpi_factor).round() as i32,
+            y: (rect.y * hidpi_factor).round() as i32,
+            w: size.w,
+            h: size.h,
+        };
+        self.set_win_normal_rect_int(win_id, rect);
+        self.win_update_snap_anchors(win_id);
     }
 
-    pub(crate) fn set_win_normal_rect(&mut self, win_id: WinId, rect: RectF) {
-        let WinId(win_idx) = win_id;
-        if let Some(win) = &mut self.window_states[win_idx as usize] {
-            win.rect = rect;
+    /// Moves a window so that the top-left corner of its normal rect is at
+    /// `position` in logical units, keeping its size. See
+    /// `set_win_normal_rect` for details.
+    pub fn set_win_position(&mut self, win_id: WinId, position: [f32; 2]) {
+        if let Some(rect) = self.win_normal_rect(win_id) {
+            let [x, y] = position;
+            self.set_win_normal_rect(win_id, RectF { x, y, ..rect });
+        }
+    }
+
+    /// Resizes the normal rect of a window to `size` in logical units,
+    /// keeping its position. See `set_win_normal_rect` for details.
+    pub fn set_win_size(&mut self, win_id: WinId, size: [f32; 2]) {
+        if let Some(rect) = self.win_normal_rect(win_id) {
+            let [w, h] = size;
+            self.set_win_normal_rect(win_id, RectF { w, h, ..rect });
         }
     }
 
     pub(crate) fn set_win_normal_rect_int(&mut self, win_id: WinId, rect: RectI) {
-        let WinId(win_idx) = win_id;
         let hidpi_factor = self.hidpi_factor as f32;
-        if let Some(win) = &mut self.window_states[win_idx as usize] {
+        if let Some(win) = self.win_state_mut(win_id) {
             win.rect = RectF {
                 x: rect.x as f32 / hidpi_factor,
                 y: rect.y as f32 / hidpi_factor,
@@ -570,16 +1209,17 @@ impl WindowingState {
     }
 
     pub fn set_win_min_size(&mut self, win_id: WinId, min_size: [f32; 2]) {
-        let WinId(win_idx) = win_id;
-        if let Some(win) = &mut self.window_states[win_idx as usize] {
+        let frame_size = self.win_frame_size(win_id);
+        if let Some(win) = self.win_state_mut(win_id) {
             let min_size: dim::SizeF = min_size.into();
+            if win.min_size == min_size {
+                return;
+            }
             if win.min_size.w < min_size.w || win.min_size.h < min_size.h {
                 // The new `min_size` is larger than the existing one, so we
                 // might need to expand the window.
-                let border_thickness = self.frame_metrics.border_thickness as f32;
-                let title_bar_height = self.frame_metrics.title_bar_height as f32;
-                let min_w = border_thickness * 2.0 + min_size.w;
-                let min_h = border_thickness * 2.0 + title_bar_height + min_size.h;
+                let min_w = frame_size.w + min_size.w;
+                let min_h = frame_size.h + min_size.h;
                 if win.rect.w < min_w {
                     win.rect.w = min_w;
                 }
@@ -588,94 +1228,304 @@ impl WindowingState {
                 }
             }
             win.min_size = min_size;
+            if win.docked_side.is_some() {
+                self.update_work_area();
+            } else {
+                self.update_tile_layout();
+            }
+        }
+    }
+
+    /// Sets the maximum client size of the window, shrinking the window if it
+    /// is larger. The maximum size does not take precedence over the minimum
+    /// size.
+    pub fn set_win_max_size(&mut self, win_id: WinId, max_size: Option<[f32; 2]>) {
+        let frame_size = self.win_frame_size(win_id);
+        if let Some(win) = self.win_state_mut(win_id) {
+            let max_size: Option<dim::SizeF> = max_size.map(|x| x.into());
+            if win.max_size == max_size {
+                return;
+            }
+            if let Some(max_size) = max_size {
+                let max_w = frame_size.w + max_size.w.max(win.min_size.w);
+                let max_h = frame_size.h + max_size.h.max(win.min_size.h);
+                if win.rect.w > max_w {
+                    win.rect.w = max_w;
+                }
+                if win.rect.h > max_h {
+                    win.rect.h = max_h;
+                }
+            }
+            win.max_size = max_size;
+            if win.docked_side.is_some() {
+                self.update_work_area();
+            } else {
+                self.update_tile_layout();
+            }
+        }
+    }
+
+    pub fn win_is_resizable(&self, win_id: WinId) -> bool {
+        self.win_state(win_id).is_some_and(|x| x.is_resizable)
+    }
+
+    /// Sets whether the window can be resized by dragging its borders.
+    pub fn set_win_resizable(&mut self, win_id: WinId, is_resizable: bool) {
+        let win = match self.win_state_mut(win_id) {
+            Some(win) => win,
+            None => return,
+        };
+        if win.is_resizable == is_resizable {
+            return;
+        }
+        win.is_resizable = is_resizable;
+        if !is_resizable {
+            if let Some((_, ht)) = self.current_dragging_win().filter(|&(id, _)| id == win_id) {
+                if ht != HitTest::TitleBarOrDragArea && ht != HitTest::TabStrip {
+                    self.win_drag_end(true);
+                }
+            }
+        }
+    }
+
+    pub fn win_is_pinned(&self, win_id: WinId) -> bool {
+        self.win_state(win_id).is_some_and(|x| x.is_pinned)
+    }
+
+    /// Sets whether the window is pinned in place. Pinned windows cannot be
+    /// moved by dragging, including their tabs and Alt+Drag, but they can
+    /// still be resized, raised and moved with `set_win_normal_rect`.
+    pub fn set_win_pinned(&mut self, win_id: WinId, is_pinned: bool) {
+        let win = match self.win_state_mut(win_id) {
+            Some(win) => win,
+            None => return,
+        };
+        if win.is_pinned == is_pinned {
+            return;
+        }
+        win.is_pinned = is_pinned;
+        if is_pinned {
+            if let Some((_, ht)) = self.current_dragging_win().filter(|&(id, _)| id == win_id) {
+                if ht == HitTest::TitleBarOrDragArea || ht == HitTest::TabStrip {
+                    self.win_drag_end(true);
+                }
+            }
+        }
+    }
+
+    /// Gets the title of the window as last set by `make_window`.
+    pub fn win_title(&self, win_id: WinId) -> Option<&str> {
+        self.win_state(win_id).map(|x| x.title.as_str())
+    }
+
+    pub(crate) fn set_win_title(&mut self, win_id: WinId, title: &str) {
+        if let Some(win) = self.win_state_mut(win_id) {
+            if win.title != title {
+                win.title = title.to_owned();
+            }
         }
     }
 
     pub fn win_is_hidden(&self, win_id: WinId) -> bool {
-        let WinId(win_idx) = win_id;
-        self.window_states[win_idx as usize]
-            .as_ref()
-            .map_or(true, |x| x.is_hidden)
+        self.win_state(win_id).is_none_or(|x| x.is_hidden)
     }
 
     pub(crate) fn set_win_hidden(&mut self, win_id: WinId, is_hidden: bool) {
-        let WinId(win_idx) = win_id;
-        let win = match &self.window_states[win_idx as usize] {
+        let win = match self.win_state_mut(win_id) {
             Some(win) => win,
             None => return,
         };
         if win.is_hidden == is_hidden {
             return;
         }
-
-        let win = self.window_states[win_idx as usize]
-            .as_mut()
-            .unwrap_or_else(|| unreachable!());
         win.is_hidden = is_hidden;
 
-        self.win_recompute_snapping_rect(win_id);
+        if win.docked_side.is_some() {
+            self.update_work_area();
+        } else {
+            self.win_recompute_snapping_rect(win_id);
+            self.update_tile_layout();
+        }
+        self.update_owner_hidden();
     }
 
     pub fn win_is_collapsed(&self, win_id: WinId) -> bool {
-        let WinId(win_idx) = win_id;
-        self.window_states[win_idx as usize]
-            .as_ref()
-            .map_or(false, |x| x.is_collapsed)
+        self.win_state(win_id).is_some_and(|x| x.is_collapsed)
     }
 
     pub(crate) fn set_win_collapsed(&mut self, win_id: WinId, is_collapsed: bool) {
-        let WinId(win_idx) = win_id;
-        let win = match &self.window_states[win_idx as usize] {
+        let win = match self.win_state_mut(win_id) {
             Some(win) => win,
             None => return,
         };
         if win.is_collapsed == is_collapsed {
             return;
         }
-
-        let win = self.window_states[win_idx as usize]
-            .as_mut()
-            .unwrap_or_else(|| unreachable!());
         win.is_collapsed = is_collapsed;
+        self.push_event(if is_collapsed {
+            WindowingEvent::Collapsed(win_id)
+        } else {
+            WindowingEvent::Expanded(win_id)
+        });
 
         self.win_recompute_snapping_rect(win_id);
+        self.update_tile_layout();
+        self.update_owner_hidden();
     }
 
-    pub fn win_z_order(&self, win_id: WinId) -> u32 {
-        let WinId(win_idx) = win_id;
-        self.window_z_orders[win_idx as usize]
+    pub fn win_is_maximized(&self, win_id: WinId) -> bool {
+        self.win_state(win_id).is_some_and(|x| x.is_maximized)
     }
 
+    /// Sets whether the window is maximized to fill the work area.
+    /// The normal rect of the window is kept so that it can be restored when
+    /// the window is no longer maximized. A maximized window is taken out of
+    /// the tiles until it is restored. Docked windows cannot be maximized.
+    pub fn set_win_maximized(&mut self, win_id: WinId, is_maximized: bool) {
+        let win = match self.win_state_mut(win_id) {
+            Some(win) => win,
+            None => return,
+        };
+        if win.is_maximized == is_maximized || (is_maximized && win.docked_side.is_some()) {
+            return;
+        }
+        win.is_maximized = is_maximized;
+        if self.current_dragging_win().map(|(id, _)| id) == Some(win_id) {
+            self.win_drag_end(true);
+        }
+        self.push_event(if is_maximized {
+            WindowingEvent::Maximized(win_id)
+        } else {
+            WindowingEvent::Restored(win_id)
+        });
+        self.update_tile_layout();
+    }
+
+    /// Gets the position of the window in the z-order, counting up from the
+    /// bottom, or `None` if `win_id` no longer refers to a window slot.
+    pub fn win_z_order(&self, win_id: WinId) -> Option<u32> {
+        if !self.win_id_is_valid(win_id) {
+            return None;
+        }
+        Some(self.window_z_orders[win_id.index()])
+    }
+
+    /// Brings the window to the top of its layer, followed by the windows it
+    /// owns.
     pub fn bring_to_top(&mut self, win_id: WinId) {
-        let WinId(win_idx) = win_id;
-        if *self
-            .bottom_to_top_list
-            .last()
-            .expect("There must already be at least one window.")
-            != win_id
-        {
-            let z_order = self.window_z_orders[win_idx as usize] as usize;
-            let subslice = &mut self.bottom_to_top_list[z_order..];
-            subslice.rotate_left(1);
-            for (i, &WinId(win)) in subslice.iter().enumerate() {
-                self.window_z_orders[win as usize] = (i + z_order) as u32;
-            }
+        if !self.win_id_is_valid(win_id) {
+            return;
+        }
+        self.active_win = Some(win_id);
+        self.mru_list.retain(|&x| x != win_id);
+        self.mru_list.insert(0, win_id);
+        let prev_z_order = self.win_z_order(win_id);
+        self.raise_with_owned(win_id);
+        self.sort_by_layer();
+        if self.win_z_order(win_id) != prev_z_order {
+            self.push_event(WindowingEvent::Raised(win_id));
+        }
+    }
+
+    pub fn win_layer(&self, win_id: WinId) -> WindowLayer {
+        self.win_state(win_id)
+            .map_or(WindowLayer::Normal, |x| x.layer)
+    }
+
+    /// Moves the window to another z-order layer. The window is placed at the
+    /// top of the new layer.
+    pub fn set_win_layer(&mut self, win_id: WinId, layer: WindowLayer) {
+        let win = match self.win_state_mut(win_id) {
+            Some(win) => win,
+            None => return,
+        };
+        if win.layer == layer {
+            return;
+        }
+        win.layer = layer;
+        let z_order = self.window_z_orders[win_id.index()] as usize;
+        let subslice = &mut self.bottom_to_top_list[z_order..];
+        subslice.rotate_left(1);
+        self.sort_by_layer();
+    }
+
+    /// Restores the order of the windows in `bottom_to_top_list` so that
+    /// windows in higher layers are above those in lower layers, and modal
+    /// windows are above all of them, keeping the order of the windows within
+    /// each layer.
+    fn sort_by_layer(&mut self) {
+        let window_states = &self.window_states;
+        let window_generations = &self.window_generations;
+        // Slots which are not in use are treated as normal windows.
+        self.bottom_to_top_list.sort_by_key(|&win_id| {
+            window_states[win_id.index()]
+                .as_ref()
+                .filter(|_| window_generations[win_id.index()] == win_id.generation)
+                .map_or((false, WindowLayer::Normal), |win| {
+                    (win.is_modal, win.layer)
+                })
+        });
+        for (i, win_id) in self.bottom_to_top_list.iter().enumerate() {
+            self.window_z_orders[win_id.index()] = i as u32;
         }
     }
 
     pub fn win_drag_start(&mut self, win_id: WinId, dragging_hit_test: HitTest) -> bool {
+        // Restarting the same drag continues it without reporting it as a new
+        // drag, unless it cannot be restarted.
+        let is_continued = self.current_dragging_win() == Some((win_id, dragging_hit_test));
+        let is_started = self.win_drag_start_impl(win_id, dragging_hit_test);
+        if is_started && !is_continued {
+            self.push_event(WindowingEvent::DragStarted {
+                win_id,
+                hit_test: dragging_hit_test,
+            });
+        } else if !is_started && is_continued {
+            self.push_event(WindowingEvent::DragEnded(win_id));
+        }
+        is_started
+    }
+
+    fn win_drag_start_impl(&mut self, win_id: WinId, dragging_hit_test: HitTest) -> bool {
         if let Some(DraggingState {
             win_id: dragging_win_id,
+            dragging_hit_test: current_hit_test,
             ..
         }) = self.maybe_dragging_window
         {
-            if dragging_win_id == win_id {
+            if dragging_win_id == win_id && dragging_hit_test == current_hit_test {
                 // Trying to drag the same window? Just continue dragging...
+                self.win_drag_end_impl(false);
+            } else if dragging_win_id == win_id {
                 self.win_drag_end(false);
             } else {
                 self.win_drag_end(true);
             }
         }
+        match dragging_hit_test {
+            HitTest::TitleBarOrDragArea | HitTest::TabStrip if self.win_is_pinned(win_id) => {
+                // Don't allow moving a pinned window.
+                return false;
+            }
+            _ => {}
+        }
+        if dragging_hit_test == HitTest::TabStrip {
+            return self.tab_drag_start(win_id);
+        }
+        match dragging_hit_test {
+            HitTest::Content | HitTest::TitleBarOrDragArea | HitTest::TabStrip => {}
+            _ if !self.win_is_resizable(win_id) => {
+                // Don't allow resizing a non-resizable window.
+                return false;
+            }
+            _ => {}
+        }
+        if let Some(side) = self.win_docked_side(win_id) {
+            return self.dock_drag_start(win_id, side, dragging_hit_test);
+        }
+        if self.win_is_tiled(win_id) && !self.win_is_maximized(win_id) {
+            return self.tile_drag_start(win_id, dragging_hit_test);
+        }
         match dragging_hit_test {
             HitTest::TopBorder
             | HitTest::LeftBorder
@@ -692,6 +1542,10 @@ impl WindowingState {
             }
             _ => {}
         }
+        if self.win_is_maximized(win_id) {
+            // Maximized windows can be neither moved nor resized.
+            return false;
+        }
         // Use the pixel-aligned `Rect` to prevent the right/bottom edge from
         // wobbling during resize due to rounding issues.
         let starting_rect = match self.win_normal_rect_int(win_id) {
@@ -700,7 +1554,7 @@ impl WindowingState {
         };
 
         let hidpi_factor = self.hidpi_factor as f32;
-        let snap_margin = (8.0 * hidpi_factor).round() as i32;
+        let snap_margin = (self.frame_metrics.snap_margin as f32 * hidpi_factor).round() as i32;
         let win_display_size = {
             match self.win_display_rect_int(win_id) {
                 Some(r) => r.size(),
@@ -778,17 +1632,12 @@ impl WindowingState {
         // Gather a list of borders of other windows that could
         // possibly be snapped to.
         // TODO: Possible optimization by filtering out impossible borders.
-        let base_iter = self
-            .window_states
-            .iter()
-            .enumerate()
-            .filter_map(|(i, _)| {
-                let i_win_id = WinId(i as u32);
-                if i_win_id != win_id {
-                    Some(i_win_id)
-                } else {
-                    None
-                }
+        let base_iter = (0..self.window_states.len())
+            .filter_map(|i| self.win_id_at(i))
+            .filter(|&i_win_id| {
+                i_win_id != win_id
+                    && !self.win_is_maximized(i_win_id)
+                    && self.win_docked_side(i_win_id).is_none()
             })
             .filter_map(|win_id| {
                 let rect = self.win_display_rect_int(win_id)?;
@@ -814,72 +1663,122 @@ impl WindowingState {
             last_snapped_x: None,
             snap_candidates_y,
             last_snapped_y: None,
+            drop_target: None,
         });
         true
     }
 
     pub fn win_drag_end(&mut self, abort: bool) {
+        if let Some((win_id, _)) = self.current_dragging_win() {
+            self.push_event(if abort {
+                WindowingEvent::DragAborted(win_id)
+            } else {
+                WindowingEvent::DragEnded(win_id)
+            });
+        }
+        self.win_drag_end_impl(abort);
+    }
+
+    fn win_drag_end_impl(&mut self, abort: bool) {
         let DraggingState {
             win_id,
+            dragging_hit_test,
             starting_rect,
+            drop_target,
             ..
         } = match self.maybe_dragging_window.take() {
             Some(x) => x,
             None => return,
         };
+        if dragging_hit_test == HitTest::TabStrip {
+            // The tab has not been dragged far enough to be detached.
+            return;
+        }
+        if let Some(side) = self.win_docked_side(win_id) {
+            if abort {
+                self.set_win_dock_size_int(win_id, side, starting_rect);
+            }
+            return;
+        }
+        if self.win_is_tiled(win_id) {
+            if abort {
+                self.tile_drag_update(win_id, dragging_hit_test, starting_rect, [0.0, 0.0]);
+            }
+            return;
+        }
         if abort {
-            self.set_win_normal_rect_int(win_id, starting_rect);
+            if dragging_hit_test == HitTest::TitleBarOrDragArea {
+                self.set_win_normal_rect_int_with_followers(win_id, starting_rect);
+            } else {
+                self.set_win_normal_rect_int(win_id, starting_rect);
+            }
+        } else if let Some(target_win_id) = drop_target {
+            self.set_win_normal_rect_int_with_followers(win_id, starting_rect);
+            if self.merge_into_tab_group(win_id, target_win_id) {
+                self.bring_to_top(win_id);
+            }
         } else {
             // Check whether the window was snapped to the area edges.
-            let rect = match self.win_normal_rect_int(win_id) {
-                Some(r) => r,
-                None => return,
-            };
-            let display_size = match self.win_display_rect_int(win_id) {
-                Some(r) => r.size(),
-                None => return,
-            };
-            let WinId(win_idx) = win_id;
-            let hidpi_factor = self.hidpi_factor as f32;
-            let snap_margin = (8.0 * hidpi_factor).round() as i32;
-            let area_size = dim::Size::from([
-                (self.area_size[0] * hidpi_factor) as i32,
-                (self.area_size[1] * hidpi_factor) as i32,
-            ]);
-
-            fn check_snap_anchor<D: dim::Dir>(
-                rect: RectI,
-                display_size: dim::SizeI,
-                area_size: dim::SizeI,
-                snap_margin: i32,
-            ) -> snapping::Anchor {
-                let pos = rect.pos().dim::<D>();
-                let is_snap_lower = pos == 0 + snap_margin;
-                let is_snap_upper =
-                    pos + display_size.dim::<D>() == area_size.dim::<D>() - snap_margin;
-                match (is_snap_lower, is_snap_upper) {
-                    (true, true) => snapping::Anchor::LowerAndUpperEdges,
-                    (true, false) => snapping::Anchor::LowerEdge,
-                    (false, true) => snapping::Anchor::UpperEdge,
-                    (false, false) => snapping::Anchor::None,
-                }
-            }
+            self.win_update_snap_anchors(win_id);
+        }
+    }
+
+    /// Sets the anchors of a window according to whether its normal rect is
+    /// snapped to the edges of the work area, and rounds the normal rect to
+    /// the physical pixel grid.
+    fn win_update_snap_anchors(&mut self, win_id: WinId) {
+        let rect = match self.win_normal_rect_int(win_id) {
+            Some(r) => r,
+            None => return,
+        };
+        let display_size = match self.win_state(win_id) {
+            Some(win) => self.win_normal_display_size_int(win, rect),
+            None => return,
+        };
+        let hidpi_factor = self.hidpi_factor as f32;
+        let snap_margin = (self.frame_metrics.snap_margin as f32 * hidpi_factor).round() as i32;
+        let work_area = self.work_area_int();
 
-            let anchor_x =
-                check_snap_anchor::<dim::Horizontal>(rect, display_size, area_size, snap_margin);
-            let anchor_y =
-                check_snap_anchor::<dim::Vertical>(rect, display_size, area_size, snap_margin);
+        fn check_snap_anchor<D: dim::Dir>(
+            rect: RectI,
+            display_size: dim::SizeI,
+            work_area: RectI,
+            snap_margin: i32,
+        ) -> snapping::Anchor {
+            let pos = rect.pos().dim::<D>();
+            let area_range = work_area.range::<D>();
+            let is_snap_lower = pos == area_range.lower() + snap_margin;
+            let is_snap_upper = pos + display_size.dim::<D>() == area_range.upper() - snap_margin;
+            match (is_snap_lower, is_snap_upper) {
+                (true, true) => snapping::Anchor::LowerAndUpperEdges,
+                (true, false) => snapping::Anchor::LowerEdge,
+                (false, true) => snapping::Anchor::UpperEdge,
+                (false, false) => snapping::Anchor::None,
+            }
+        }
 
-            let win = match self.window_states[win_idx as usize].as_mut() {
-                Some(x) => x,
-                None => unreachable!(),
-            };
-            win.anchor_x = anchor_x;
-            win.anchor_y = anchor_y;
+        let anchor_x =
+            check_snap_anchor::<dim::Horizontal>(rect, display_size, work_area, snap_margin);
+        let anchor_y =
+            check_snap_anchor::<dim::Vertical>(rect, display_size, work_area, snap_margin);
 
-            // Round to device pixel.
-            self.set_win_normal_rect_int(win_id, rect);
+        let win = match self.win_state_mut(win_id) {
+            Some(x) => x,
+            None => unreachable!(),
+        };
+        let is_anchor_changed = win.anchor_x != anchor_x || win.anchor_y != anchor_y;
+        win.anchor_x = anchor_x;
+        win.anchor_y = anchor_y;
+        if is_anchor_changed {
+            self.push_event(WindowingEvent::Anchored {
+                win_id,
+                is_anchored: anchor_x != snapping::Anchor::None
+                    || anchor_y != snapping::Anchor::None,
+            });
         }
+
+        // Round to device pixel.
+        self.set_win_normal_rect_int(win_id, rect);
     }
 
     pub fn current_dragging_win(&self) -> Option<(WinId, HitTest)> {
@@ -898,6 +1797,15 @@ impl WindowingState {
             Some(x) => x,
             None => return false,
         };
+        if dragging_hit_test == HitTest::TabStrip {
+            return self.tab_drag_update(win_id, offset);
+        }
+        if let Some(side) = self.win_docked_side(win_id) {
+            return self.dock_drag_update(win_id, side, starting_rect, offset);
+        }
+        if self.win_is_tiled(win_id) {
+            return self.tile_drag_update(win_id, dragging_hit_test, starting_rect, offset);
+        }
         let prev_rect = match self.win_display_rect_int(win_id) {
             Some(x) => x,
             None => {
@@ -913,30 +1821,59 @@ impl WindowingState {
         // Ensure the window being dragged is topmost.
         self.bring_to_top(win_id);
 
-        let border_thickness = self.frame_metrics.border_thickness as f32;
-        let title_bar_height = self.frame_metrics.title_bar_height as f32;
+        let work_area = self.work_area_int();
 
-        let area_size = dim::SizeI {
-            w: (self.area_size[0] * hidpi_factor) as i32,
-            h: (self.area_size[1] * hidpi_factor) as i32,
+        let win = self.win_state(win_id).unwrap_or_else(|| unreachable!());
+        let (min_size, max_size) = self.win_size_limits_int(win_id);
+        let aspect_ratio = win.aspect_ratio;
+        let frame_size = self.win_frame_size_int(win_id);
+        // Only allow the sizes for which the other dimension can follow the
+        // aspect ratio.
+        let (narrowed_min_size, narrowed_max_size) = match aspect_ratio {
+            Some(aspect_ratio) => aspect_ratio::limit_sizes_to_aspect_ratio(
+                min_size,
+                max_size,
+                frame_size,
+                aspect_ratio,
+            ),
+            None => (min_size, max_size),
         };
 
-        let win = self.window_states[win_id.0 as usize]
-            .as_ref()
-            .unwrap_or_else(|| unreachable!());
-        let min_w = ((border_thickness * 2.0 + win.min_size.w) * hidpi_factor).round() as i32;
-        let min_h = ((border_thickness * 2.0 + title_bar_height + win.min_size.h) * hidpi_factor)
-            .round() as i32;
-
-        // TODO: Make these configurable:
-        let snap_threshold = (12.0 * hidpi_factor).round() as i32;
-        let snap_margin = (8.0 * hidpi_factor).round() as i32;
+        let snap_threshold =
+            (self.frame_metrics.snap_threshold as f32 * hidpi_factor).round() as i32;
+        let snap_margin = (self.frame_metrics.snap_margin as f32 * hidpi_factor).round() as i32;
+
+        let limits_x = DragLimits::<dim::Horizontal> {
+            min_size: narrowed_min_size.w,
+            max_size: narrowed_max_size.w,
+            other_min_size: min_size.h,
+            other_max_size: max_size.h,
+            aspect_ratio: aspect_ratio.map(|aspect_ratio| (aspect_ratio, frame_size)),
+            area_range: work_area.range(),
+            snap_margin,
+            snap_threshold,
+        };
+        let limits_y = DragLimits::<dim::Vertical> {
+            min_size: narrowed_min_size.h,
+            max_size: narrowed_max_size.h,
+            other_min_size: min_size.w,
+            other_max_size: max_size.w,
+            aspect_ratio: aspect_ratio.map(|aspect_ratio| (aspect_ratio, frame_size)),
+            area_range: work_area.range(),
+            snap_margin,
+            snap_threshold,
+        };
 
         let dragging_state = self
             .maybe_dragging_window
             .as_mut()
             .unwrap_or_else(|| unreachable!());
+        let prev_snapped_x = dragging_state.last_snapped_x;
+        let prev_snapped_y = dragging_state.last_snapped_y;
 
+        /// Tries to snap to the edges of other windows. `try_snap` checks
+        /// whether a candidate edge is within reach and keeps the window
+        /// within its size limits.
         fn snap_dimension<D: dim::Dir>(
             try_snap: impl Fn(i32) -> Option<i32>,
             dim_range: dim::DimRange<i32, D>,
@@ -975,61 +1912,51 @@ impl WindowingState {
                     None
                 }
             })
-        };
+        }
 
+        /// Returns the new position and size along `D`, and whether an edge
+        /// has been snapped.
         fn calc_new_dimensions<D: dim::Dir>(
             dragging_hit_test: HitTest,
             starting_rect: RectI,
             prev_display_rect: RectI,
             delta: i32,
-            win_min_size: i32,
-            area_size: dim::SizeI,
-            snap_margin: i32,
-            snap_threshold: i32,
+            limits: &DragLimits<D>,
             snap_candidates: &[(WinId, snapping::SnapSegment<D::PerpendicularDir>)],
             last_snapped: &mut Option<u32>,
-        ) -> (i32, i32) {
+        ) -> (i32, i32, bool) {
+            let area_range = limits.area_range;
+            let snap_margin = limits.snap_margin;
             let snap_move = |pos: i32, edge: i32| {
-                if (pos - edge).abs() < snap_threshold {
+                if (pos - edge).abs() < limits.snap_threshold {
                     Some(edge)
                 } else {
                     None
                 }
             };
-            let snap_resize_upper = |lower_pos: i32, upper_pos: i32, edge: i32, min_dim: i32| {
-                snap_move(upper_pos, edge).and_then(|edge| {
-                    if (edge - lower_pos) < min_dim {
-                        None
-                    } else {
-                        Some(edge)
-                    }
-                })
+            let snap_resize_upper = |lower_pos: i32, upper_pos: i32, edge: i32| {
+                snap_move(upper_pos, edge).filter(|&edge| limits.accepts_size(edge - lower_pos))
             };
-            let snap_resize_lower = |lower_pos: i32, upper_pos: i32, edge: i32, min_dim: i32| {
-                snap_move(lower_pos, edge).and_then(|edge| {
-                    if (upper_pos - edge) < min_dim {
-                        None
-                    } else {
-                        Some(edge)
-                    }
-                })
+            let snap_resize_lower = |lower_pos: i32, upper_pos: i32, edge: i32| {
+                snap_move(lower_pos, edge).filter(|&edge| limits.accepts_size(upper_pos - edge))
             };
             let dim_range = prev_display_rect.range::<D::PerpendicularDir>();
-            let (new_pos, new_size);
+            let (new_pos, new_size, is_snapped);
             match dragging_hit_test.to_drag_action_1d::<D>() {
                 WindowDragAction1D::None => {
                     new_pos = starting_rect.pos().dim::<D>();
                     new_size = starting_rect.size().dim::<D>();
+                    is_snapped = false;
                 }
                 WindowDragAction1D::MoveWindow => {
                     let target_pos = starting_rect.pos().dim::<D>() + delta;
                     let try_snap = |pos_to_snap: i32| snap_move(target_pos, pos_to_snap);
 
-                    new_pos = {
+                    let maybe_snap = {
                         // Try snapping to the lower and upper edges of area.
-                        let maybe_snap = try_snap(0 + snap_margin).or_else(|| {
+                        let maybe_snap = try_snap(area_range.lower() + snap_margin).or_else(|| {
                             try_snap(
-                                area_size.dim::<D>()
+                                area_range.upper()
                                     - snap_margin
                                     - prev_display_rect.size().dim::<D>(),
                             )
@@ -1039,11 +1966,10 @@ impl WindowingState {
                         }
                         maybe_snap
                     }
-                    .or_else(|| snap_dimension(try_snap, dim_range, snap_candidates, last_snapped))
-                    .unwrap_or_else(|| {
-                        // Nothing to snap
-                        target_pos
-                    });
+                    .or_else(|| snap_dimension(try_snap, dim_range, snap_candidates, last_snapped));
+                    is_snapped = maybe_snap.is_some();
+                    // Nothing to snap
+                    new_pos = maybe_snap.unwrap_or(target_pos);
                     new_size = starting_rect.size().dim::<D>();
                 }
                 WindowDragAction1D::ResizeLower => {
@@ -1053,23 +1979,25 @@ impl WindowingState {
                             target_pos,
                             starting_rect.pos().dim::<D>() + starting_rect.size().dim::<D>(),
                             pos_to_snap,
-                            win_min_size,
                         )
                     };
 
-                    new_pos = ({
+                    let maybe_snap = ({
                         // Try snapping to lower edge of area.
-                        let maybe_snap = try_snap(0 + snap_margin);
+                        let maybe_snap = try_snap(area_range.lower() + snap_margin);
                         if maybe_snap.is_some() {
                             *last_snapped = None;
                         }
                         maybe_snap
                     })
-                    .or_else(|| snap_dimension(try_snap, dim_range, snap_candidates, last_snapped))
-                    .unwrap_or_else(|| {
+                    .or_else(|| snap_dimension(try_snap, dim_range, snap_candidates, last_snapped));
+                    is_snapped = maybe_snap.is_some();
+                    new_pos = maybe_snap.unwrap_or_else(|| {
                         // Nothing to snap.
                         starting_rect.pos().dim::<D>() + starting_rect.size().dim::<D>()
-                            - (starting_rect.size().dim::<D>() - delta).max(win_min_size)
+                            - (starting_rect.size().dim::<D>() - delta)
+                                .max(limits.min_size)
+                                .min(limits.max_size)
                     });
                     new_size =
                         starting_rect.size().dim::<D>() + starting_rect.pos().dim::<D>() - new_pos;
@@ -1078,57 +2006,50 @@ impl WindowingState {
                     let target_pos =
                         starting_rect.pos().dim::<D>() + starting_rect.size().dim::<D>() + delta;
                     let try_snap = |pos_to_snap: i32| {
-                        snap_resize_upper(
-                            starting_rect.pos().dim::<D>(),
-                            target_pos,
-                            pos_to_snap,
-                            win_min_size,
-                        )
+                        snap_resize_upper(starting_rect.pos().dim::<D>(), target_pos, pos_to_snap)
                     };
 
                     new_pos = starting_rect.pos().dim::<D>();
-                    new_size = ({
+                    let maybe_snap = ({
                         // Try snapping to upper edge of area.
-                        let maybe_snap = try_snap(area_size.dim::<D>() - snap_margin);
+                        let maybe_snap = try_snap(area_range.upper() - snap_margin);
                         if maybe_snap.is_some() {
                             *last_snapped = None;
                         }
                         maybe_snap
                     })
-                    .or_else(|| snap_dimension(try_snap, dim_range, snap_candidates, last_snapped))
-                    .map(|pos| pos - starting_rect.pos().dim::<D>())
-                    .unwrap_or_else(|| {
-                        // Nothing to snap.
-                        (starting_rect.size().dim::<D>() + delta).max(win_min_size)
-                    });
+                    .or_else(|| snap_dimension(try_snap, dim_range, snap_candidates, last_snapped));
+                    is_snapped = maybe_snap.is_some();
+                    new_size = maybe_snap
+                        .map(|pos| pos - starting_rect.pos().dim::<D>())
+                        .unwrap_or_else(|| {
+                            // Nothing to snap.
+                            (starting_rect.size().dim::<D>() + delta)
+                                .max(limits.min_size)
+                                .min(limits.max_size)
+                        });
                 }
             }
-            (new_pos, new_size)
+            (new_pos, new_size, is_snapped)
         }
 
         // Calculate horizontal dimensions:=
-        let (new_x, new_w) = calc_new_dimensions::<dim::Horizontal>(
+        let (new_x, new_w, is_snapped_x) = calc_new_dimensions::<dim::Horizontal>(
             dragging_hit_test,
             starting_rect,
             prev_rect,
             dx,
-            min_w,
-            area_size,
-            snap_margin,
-            snap_threshold,
+            &limits_x,
             &dragging_state.snap_candidates_x,
             &mut dragging_state.last_snapped_x,
         );
         // Calculate vertical dimensions:
-        let (new_y, new_h) = calc_new_dimensions::<dim::Vertical>(
+        let (new_y, new_h, is_snapped_y) = calc_new_dimensions::<dim::Vertical>(
             dragging_hit_test,
             starting_rect,
             prev_rect,
             dy,
-            min_h,
-            area_size,
-            snap_margin,
-            snap_threshold,
+            &limits_y,
             &dragging_state.snap_candidates_y,
             &mut dragging_state.last_snapped_y,
         );
@@ -1139,16 +2060,105 @@ impl WindowingState {
             w: new_w,
             h: new_h,
         };
-        self.set_win_normal_rect_int(win_id, new_rect);
+        let new_rect = match aspect_ratio {
+            Some(aspect_ratio) => {
+                let snapped_dir = match (is_snapped_x, is_snapped_y) {
+                    (true, false) => Some(dim::Direction::Horizontal),
+                    (false, true) => Some(dim::Direction::Vertical),
+                    _ => None,
+                };
+                match aspect_ratio::aspect_ratio_driving_dir(
+                    dragging_hit_test,
+                    starting_rect,
+                    new_rect,
+                    snapped_dir,
+                    aspect_ratio,
+                ) {
+                    Some(driving_dir) => {
+                        // The snap of the dimension following the aspect
+                        // ratio is undone.
+                        match driving_dir {
+                            dim::Direction::Horizontal => dragging_state.last_snapped_y = None,
+                            dim::Direction::Vertical => dragging_state.last_snapped_x = None,
+                        }
+                        aspect_ratio::apply_aspect_ratio(
+                            dragging_hit_test,
+                            starting_rect,
+                            new_rect,
+                            driving_dir,
+                            frame_size,
+                            aspect_ratio,
+                        )
+                    }
+                    None => new_rect,
+                }
+            }
+            None => new_rect,
+        };
+
+        let newly_snapped_x = dragging_state
+            .last_snapped_x
+            .filter(|&i| prev_snapped_x != Some(i))
+            .map(|i| dragging_state.snap_candidates_x[i as usize].0);
+        let newly_snapped_y = dragging_state
+            .last_snapped_y
+            .filter(|&i| prev_snapped_y != Some(i))
+            .map(|i| dragging_state.snap_candidates_y[i as usize].0);
+        for target in newly_snapped_x.into_iter().chain(newly_snapped_y) {
+            self.push_event(WindowingEvent::Snapped { win_id, target });
+        }
+
+        if dragging_hit_test == HitTest::TitleBarOrDragArea {
+            self.set_win_normal_rect_int_with_followers(win_id, new_rect);
+        } else {
+            self.set_win_normal_rect_int(win_id, new_rect);
+        }
         true
     }
 }
 
+/// The limits of the size of a window being dragged along `D`, and the
+/// snapping of its edges.
+struct DragLimits<D: dim::Dir> {
+    min_size: i32,
+    max_size: i32,
+    /// The limits of the size along the perpendicular dimension, which has to
+    /// follow the size along `D` if the client aspect ratio is locked.
+    other_min_size: i32,
+    other_max_size: i32,
+    /// The client aspect ratio with the size of the window frame.
+    aspect_ratio: Option<(f32, dim::SizeI)>,
+    area_range: dim::DimRange<i32, D>,
+    snap_margin: i32,
+    snap_threshold: i32,
+}
+
+impl<D: dim::Dir> DragLimits<D> {
+    /// Checks whether the window can be resized to `size` along `D`, with
+    /// the size along the perpendicular dimension following the aspect
+    /// ratio within its limits.
+    fn accepts_size(&self, size: i32) -> bool {
+        if size < self.min_size || size > self.max_size {
+            return false;
+        }
+        match self.aspect_ratio {
+            Some((aspect_ratio, frame_size)) => {
+                let other_size =
+                    aspect_ratio::follow_aspect_ratio::<D>(size, frame_size, aspect_ratio);
+                other_size >= self.other_min_size && other_size <= self.other_max_size
+            }
+            None => true,
+        }
+    }
+}
+
 fn window_hit_test(
     window_size: [f32; 2],
     rel_pos: [f32; 2],
     hidpi_factor: f32,
     frame_metrics: FrameMetrics,
+    is_resizable: bool,
+    has_tab_strip: bool,
 ) -> Option<HitTest> {
     let [log_w, log_h] = window_size;
     let [log_x, log_y] = rel_pos;
@@ -1163,6 +2173,31 @@ fn window_hit_test(
     let border_thickness = (frame_metrics.border_thickness as f32 * hidpi_factor).round() as i32;
     let title_bar_height = (frame_metrics.title_bar_height as f32 * hidpi_factor).round() as i32;
 
+    if has_tab_strip {
+        let gap_below_title_bar =
+            (frame_metrics.gap_below_title_bar as f32 * hidpi_factor).round() as i32;
+        let tab_strip_height =
+            (frame_metrics.tab_strip_height as f32 * hidpi_factor).round() as i32;
+        let strip_top = border_thickness + title_bar_height + gap_below_title_bar;
+        if y > strip_top
+            && y <= strip_top + tab_strip_height
+            && x > border_thickness
+            && x <= w - border_thickness
+        {
+            return Some(HitTest::TabStrip);
+        }
+    }
+
+    if !is_resizable {
+        // Without resizable borders, the top border is treated as part of the
+        // title bar and the other borders as part of the content.
+        return Some(if y <= border_thickness + title_bar_height {
+            HitTest::TitleBarOrDragArea
+        } else {
+            HitTest::Content
+        });
+    }
+
     let window_part_x = if x <= border_thickness {
         WindowPartX::LeftBorder
     } else if x > w - border_thickness {
@@ -1217,3 +2252,283 @@ fn window_hit_test(
         _ => HitTest::TitleBarOrDragArea,
     })
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Makes a window with a client area of 200x100 at `position`, which the
+    /// tests of the submodules share.
+    pub(super) fn init_win(state: &mut WindowingState, position: [f32; 2]) -> WinId {
+        let win_id = state.next_id();
+        state.ensure_init(win_id, || WindowInitialState {
+            client_size: [200.0, 100.0],
+            position: Some(position),
+            min_size: None,
+            is_collapsed: false,
+        });
+        win_id
+    }
+
+    #[test]
+    fn test_stale_win_id_does_not_alias() {
+        let mut state = WindowingState::new();
+        state.set_dimensions([800.0, 600.0], 1.0);
+        let win1 = init_win(&mut state, [10.0, 10.0]);
+        let win2 = init_win(&mut state, [10.0, 10.0]);
+
+        // Stop using `win1` so that it gets destroyed.
+        state.set_all_needed(false);
+        state.set_needed(win2, true);
+        state.sweep_unneeded();
+        assert!(!state.win_id_is_valid(win1));
+        assert!(state.win_id_is_valid(win2));
+
+        let win3 = init_win(&mut state, [10.0, 10.0]);
+        assert_eq!(win3.index(), win1.index());
+        assert_ne!(win3, win1);
+        assert!(state.win_display_rect(win1).is_none());
+        assert!(state.win_display_rect(win3).is_some());
+        assert_eq!(state.win_z_order(win1), None);
+        assert!(state.win_z_order(win3).is_some());
+
+        state.bring_to_top(win2);
+        state.bring_to_top(win1);
+        assert_eq!(state.topmost_win(), Some(win2));
+    }
+
+    #[test]
+    fn test_maximize_and_restore() {
+        let mut state = WindowingState::new();
+        state.set_dimensions([800.0, 600.0], 1.0);
+        let win_id = init_win(&mut state, [10.0, 10.0]);
+        let normal_rect = state.win_display_rect(win_id).unwrap();
+
+        state.set_win_maximized(win_id, true);
+        assert!(state.win_is_maximized(win_id));
+        let rect = state.win_display_rect(win_id).unwrap();
+        assert_eq!((rect.x, rect.y, rect.w, rect.h), (0.0, 0.0, 800.0, 600.0));
+        assert!(!state.win_drag_start(win_id, HitTest::TitleBarOrDragArea));
+
+        // The maximized window follows the size of the windowing area.
+        state.set_dimensions([640.0, 480.0], 1.0);
+        let rect = state.win_display_rect(win_id).unwrap();
+        assert_eq!((rect.w, rect.h), (640.0, 480.0));
+
+        state.set_dimensions([800.0, 600.0], 1.0);
+        state.set_win_maximized(win_id, false);
+        assert_eq!(state.win_display_rect(win_id).unwrap(), normal_rect);
+    }
+
+    #[test]
+    fn test_set_win_rect() {
+        let mut state = WindowingState::new();
+        state.set_dimensions([800.0, 600.0], 1.5);
+        let win_id = init_win(&mut state, [10.0, 10.0]);
+
+        // The position is rounded to the physical pixel grid.
+        state.set_win_position(win_id, [100.2, 50.0]);
+        let rect = state.win_normal_rect(win_id).unwrap();
+        assert_eq!((rect.x * 1.5, rect.y), (150.0, 50.0));
+
+        // The size is limited by the minimum size.
+        state.set_win_size(win_id, [10.0, 10.0]);
+        let min_rect = state.win_normal_rect(win_id).unwrap();
+        assert!(min_rect.w > 150.0 && min_rect.h > 50.0);
+        assert_eq!(min_rect.pos(), rect.pos());
+
+        // Placing the window at the margin from the right edge anchors it.
+        state.set_win_normal_rect(
+            win_id,
+            RectF {
+                x: 800.0 - 8.0 - 300.0,
+                y: 100.0,
+                w: 300.0,
+                h: 200.0,
+            },
+        );
+        state.set_dimensions([1000.0, 600.0], 1.5);
+        let rect = state.win_normal_rect(win_id).unwrap();
+        assert_eq!((rect.x, rect.w), (1000.0 - 8.0 - 300.0, 300.0));
+
+        // A maximized window is restored, and the height follows the locked
+        // aspect ratio.
+        state.set_win_maximized(win_id, true);
+        state.set_win_aspect_ratio(win_id, Some(2.0));
+        state.set_win_size(win_id, [400.0, 400.0]);
+        assert!(!state.win_is_maximized(win_id));
+        let frame_size = state.win_frame_size_int(win_id);
+        let rect = state.win_normal_rect_int(win_id).unwrap();
+        assert_eq!(rect.w - frame_size.w, (rect.h - frame_size.h) * 2);
+
+        // A docked window is undocked.
+        state.set_win_docked(win_id, Some(DockSide::Left));
+        state.set_win_position(win_id, [100.0, 100.0]);
+        assert_eq!(state.win_docked_side(win_id), None);
+        assert_eq!(state.win_display_rect(win_id).unwrap().x, 100.0);
+    }
+
+    #[test]
+    fn test_max_size_and_non_resizable() {
+        let mut state = WindowingState::new();
+        state.set_dimensions([800.0, 600.0], 1.0);
+        let win_id = init_win(&mut state, [10.0, 10.0]);
+        let border_thickness = state.frame_metrics.border_thickness as f32;
+
+        // The maximum size includes the whole frame, so a window already at
+        // its maximum size is kept as is, also when the aspect ratio is set.
+        let rect = state.win_normal_rect(win_id).unwrap();
+        state.set_win_max_size(win_id, Some([200.0, 100.0]));
+        assert_eq!(state.win_normal_rect(win_id).unwrap(), rect);
+        state.set_win_aspect_ratio(win_id, Some(2.0));
+        assert!(state.win_drag_start(win_id, HitTest::BottomBorder));
+        assert!(state.win_drag_update([0.0, 50.0]));
+        state.win_drag_end(false);
+        assert_eq!(state.win_normal_rect(win_id).unwrap(), rect);
+        state.set_win_aspect_ratio(win_id, None);
+
+        // Setting a smaller maximum size shrinks the window.
+        state.set_win_max_size(win_id, Some([180.0, 300.0]));
+        let rect = state.win_normal_rect(win_id).unwrap();
+        assert_eq!(rect.w, 180.0 + border_thickness * 2.0);
+
+        // Resizing is limited by the maximum size, even when snapping to the
+        // edge of the area.
+        state.set_win_max_size(win_id, Some([400.0, 300.0]));
+        assert!(state.win_drag_start(win_id, HitTest::RightBorder));
+        assert!(state.win_drag_update([1000.0, 0.0]));
+        state.win_drag_end(false);
+        let rect = state.win_normal_rect(win_id).unwrap();
+        assert_eq!(rect.w, 400.0 + border_thickness * 2.0);
+        assert!(state.win_drag_start(win_id, HitTest::RightBorder));
+        assert!(state.win_drag_update([800.0 - 8.0 - rect.x - rect.w, 0.0]));
+        state.win_drag_end(false);
+        assert_eq!(state.win_normal_rect(win_id).unwrap(), rect);
+
+        state.set_win_resizable(win_id, false);
+        assert!(!state.win_drag_start(win_id, HitTest::RightBorder));
+        let pos = [rect.x + rect.w - 1.0, rect.y + rect.h / 2.0];
+        assert_eq!(state.win_hit_test(pos), Some((win_id, HitTest::Content)));
+        assert!(state.win_drag_start(win_id, HitTest::TitleBarOrDragArea));
+    }
+
+    #[test]
+    fn test_pinned_window() {
+        let mut state = WindowingState::new();
+        state.set_dimensions([800.0, 600.0], 1.0);
+        let win_id = init_win(&mut state, [10.0, 10.0]);
+        let rect = state.win_normal_rect(win_id).unwrap();
+
+        state.set_win_pinned(win_id, true);
+        assert!(state.win_is_pinned(win_id));
+        assert!(!state.win_drag_start(win_id, HitTest::TitleBarOrDragArea));
+        assert!(!state.win_drag_update([50.0, 50.0]));
+        assert_eq!(state.win_normal_rect(win_id).unwrap(), rect);
+
+        // Pinned windows can still be resized.
+        assert!(state.win_drag_start(win_id, HitTest::RightBorder));
+        assert!(state.win_drag_update([50.0, 0.0]));
+        state.win_drag_end(false);
+        assert_eq!(state.win_normal_rect(win_id).unwrap().w, rect.w + 50.0);
+
+        state.set_win_pinned(win_id, false);
+        assert!(state.win_drag_start(win_id, HitTest::TitleBarOrDragArea));
+    }
+
+    #[test]
+    fn test_window_layers() {
+        let mut state = WindowingState::new();
+        state.set_dimensions([800.0, 600.0], 1.0);
+        let palette = init_win(&mut state, [10.0, 10.0]);
+        let win1 = init_win(&mut state, [10.0, 10.0]);
+        let win2 = init_win(&mut state, [10.0, 10.0]);
+        let background = init_win(&mut state, [10.0, 10.0]);
+        state.set_win_layer(palette, WindowLayer::Floating);
+        state.set_win_layer(background, WindowLayer::Background);
+        assert_eq!(state.topmost_win(), Some(palette));
+        assert_eq!(state.win_z_order(background), Some(0));
+
+        // Raising a window only brings it to the top of its layer.
+        state.bring_to_top(win1);
+        state.bring_to_top(background);
+        assert_eq!(state.topmost_win(), Some(palette));
+        assert_eq!(state.active_win(), Some(background));
+        assert_eq!(state.win_z_order(background), Some(0));
+        assert!(state.win_z_order(win1) > state.win_z_order(win2));
+        assert!(state.win_z_order(palette) > state.win_z_order(win1));
+
+        // All the windows overlap, so the hit test finds the palette.
+        assert_eq!(state.win_hit_test([100.0, 100.0]).unwrap().0, palette);
+
+        state.set_win_layer(palette, WindowLayer::Normal);
+        assert_eq!(state.topmost_win(), Some(palette));
+        state.bring_to_top(win2);
+        assert_eq!(state.win_hit_test([100.0, 100.0]).unwrap().0, win2);
+    }
+
+    #[test]
+    fn test_mru_order() {
+        let mut state = WindowingState::new();
+        state.set_dimensions([800.0, 600.0], 1.0);
+        let palette = init_win(&mut state, [10.0, 10.0]);
+        let win1 = init_win(&mut state, [10.0, 10.0]);
+        let win2 = init_win(&mut state, [10.0, 10.0]);
+        state.set_win_layer(palette, WindowLayer::Floating);
+        let mru = |state: &WindowingState| state.mru_wins().collect::<Vec<_>>();
+        assert_eq!(mru(&state), [win2, win1, palette]);
+
+        // The MRU order is not affected by the layers.
+        state.bring_to_top(palette);
+        state.bring_to_top(win1);
+        assert_eq!(state.topmost_win(), Some(palette));
+        assert_eq!(mru(&state), [win1, palette, win2]);
+
+        // Hidden and destroyed windows are skipped.
+        state.set_win_hidden(palette, true);
+        assert_eq!(mru(&state), [win1, win2]);
+        state.set_all_needed(true);
+        state.set_needed(win1, false);
+        state.sweep_unneeded();
+        assert_eq!(mru(&state), [win2]);
+    }
+
+    #[test]
+    fn test_frame_metrics_config() {
+        let mut state = WindowingState::new();
+        state.set_dimensions([800.0, 600.0], 1.5);
+        let win_id = init_win(&mut state, [10.0, 10.0]);
+        let pos = state.win_display_rect(win_id).unwrap().pos();
+        let hit_test_at = |state: &WindowingState, y: f32| {
+            state.specific_win_hit_test(win_id, [pos.x + 100.0, pos.y + y])
+        };
+        assert_eq!(hit_test_at(&state, 25.0), Some(HitTest::Content));
+
+        state.set_frame_metrics_config(FrameMetricsConfig {
+            border_thickness: 3.0,
+            title_bar_height: 24.0,
+            collapsed_width: 100.0,
+            ..FrameMetricsConfig::default()
+        });
+        // The border is scaled by whole physical pixels.
+        let frame_metrics = state.frame_metrics();
+        assert_eq!(frame_metrics.border_thickness * 1.5, 3.0);
+        assert_eq!(frame_metrics.title_bar_height, 24.0);
+        assert_eq!(hit_test_at(&state, 1.0), Some(HitTest::TopBorder));
+        assert_eq!(hit_test_at(&state, 25.0), Some(HitTest::TitleBarOrDragArea));
+
+        // The snap margin is configurable.
+        state.set_frame_metrics_config(FrameMetricsConfig {
+            snap_margin: 0.0,
+            ..state.frame_metrics_config()
+        });
+        assert!(state.win_drag_start(win_id, HitTest::TitleBarOrDragArea));
+        assert!(state.win_drag_update([-5.0, 0.0]));
+        state.win_drag_end(false);
+        assert_eq!(state.win_normal_rect(win_id).unwrap().x, 0.0);
+
+        state.set_win_collapsed(win_id, true);
+        let rect = state.win_display_rect(win_id).unwrap();
+        assert_eq!(rect.w, 104.0);
+        assert_eq!(rect.h, 2.0 + 24.0 + 2.0);
+    }
+}
@@ -0,0 +1,237 @@
+use super::{dim, HitTest, RectI, WinId, WindowingState};
+
+impl WindowingState {
+    /// Gets the client aspect ratio (width / height) that the window is locked
+    /// to, if any.
+    pub fn win_aspect_ratio(&self, win_id: WinId) -> Option<f32> {
+        self.win_state(win_id)?.aspect_ratio
+    }
+
+    /// Locks the client area of the window to the aspect ratio (width /
+    /// height) when it is resized by dragging its borders. The height of the
+    /// window is adjusted to match the ratio when it is set, and the width if
+    /// the height would be outside the size limits.
+    pub fn set_win_aspect_ratio(&mut self, win_id: WinId, aspect_ratio: Option<f32>) {
+        let aspect_ratio = aspect_ratio.filter(|&x| x.is_finite() && x > 0.0);
+        let win = match self.win_state_mut(win_id) {
+            Some(win) => win,
+            None => return,
+        };
+        if win.aspect_ratio == aspect_ratio {
+            return;
+        }
+        win.aspect_ratio = aspect_ratio;
+        if self.current_dragging_win().map(|(id, _)| id) == Some(win_id) {
+            self.win_drag_end(true);
+        }
+        if aspect_ratio.is_some() {
+            let rect = match self.win_normal_rect_int(win_id) {
+                Some(x) => x,
+                None => return,
+            };
+            let size = self.constrain_win_size_int(win_id, rect.size());
+            self.set_win_normal_rect_int(
+                win_id,
+                RectI {
+                    w: size.w,
+                    h: size.h,
+                    ..rect
+                },
+            );
+        }
+    }
+
+    /// Gets the size taken by the window frame around the client area of the
+    /// window in physical pixels.
+    pub(super) fn win_frame_size_int(&self, win_id: WinId) -> dim::SizeI {
+        let hidpi_factor = self.hidpi_factor as f32;
+        let frame_size = self.win_frame_size(win_id);
+        dim::SizeI {
+            w: (frame_size.w * hidpi_factor).round() as i32,
+            h: (frame_size.h * hidpi_factor).round() as i32,
+        }
+    }
+}
+
+/// Narrows the outer size limits of a window so that every width and height
+/// within the limits can be matched by the other dimension while keeping the
+/// client aspect ratio.
+pub(super) fn limit_sizes_to_aspect_ratio(
+    min_size: dim::SizeI,
+    max_size: dim::SizeI,
+    frame_size: dim::SizeI,
+    aspect_ratio: f32,
+) -> (dim::SizeI, dim::SizeI) {
+    let min_client_w = (min_size.w - frame_size.w)
+        .max(((min_size.h - frame_size.h) as f32 * aspect_ratio).ceil() as i32);
+    let max_client_w = max_size
+        .w
+        .saturating_sub(frame_size.w)
+        .min((max_size.h.saturating_sub(frame_size.h) as f32 * aspect_ratio).floor() as i32)
+        .max(min_client_w);
+    let client_h = |client_w: i32| (client_w as f32 / aspect_ratio).round() as i32;
+    (
+        dim::SizeI {
+            w: min_client_w + frame_size.w,
+            h: client_h(min_client_w) + frame_size.h,
+        },
+        dim::SizeI {
+            w: max_client_w.saturating_add(frame_size.w),
+            h: client_h(max_client_w).saturating_add(frame_size.h),
+        },
+    )
+}
+
+/// Gets the outer size of a window along the dimension perpendicular to `D`
+/// which keeps the client aspect ratio for the outer size `size` along `D`.
+pub(super) fn follow_aspect_ratio<D: dim::Dir>(
+    size: i32,
+    frame_size: dim::SizeI,
+    aspect_ratio: f32,
+) -> i32 {
+    match D::DIR {
+        dim::Direction::Horizontal => {
+            ((size - frame_size.w) as f32 / aspect_ratio).round() as i32 + frame_size.h
+        }
+        dim::Direction::Vertical => {
+            ((size - frame_size.h) as f32 * aspect_ratio).round() as i32 + frame_size.w
+        }
+    }
+}
+
+/// Decides which dimension of `rect`, which has been resized from
+/// `starting_rect` by dragging with `dragging_hit_test`, sets the size while
+/// the other follows it to keep the client aspect ratio. The dragged edge
+/// decides the size. When dragging a corner, the dimension which has snapped
+/// to an edge takes precedence, otherwise the dimension that has changed
+/// more. Returns `None` if the window is not being resized.
+pub(super) fn aspect_ratio_driving_dir(
+    dragging_hit_test: HitTest,
+    starting_rect: RectI,
+    rect: RectI,
+    snapped_dir: Option<dim::Direction>,
+    aspect_ratio: f32,
+) -> Option<dim::Direction> {
+    match dragging_hit_test {
+        HitTest::LeftBorder | HitTest::RightBorder => Some(dim::Direction::Horizontal),
+        HitTest::TopBorder | HitTest::BottomBorder => Some(dim::Direction::Vertical),
+        HitTest::TopLeftCorner
+        | HitTest::TopRightCorner
+        | HitTest::BottomLeftCorner
+        | HitTest::BottomRightCorner => snapped_dir.or_else(|| {
+            let dw = (rect.w - starting_rect.w).abs() as f32;
+            let dh = (rect.h - starting_rect.h).abs() as f32;
+            if dw >= dh * aspect_ratio {
+                Some(dim::Direction::Horizontal)
+            } else {
+                Some(dim::Direction::Vertical)
+            }
+        }),
+        HitTest::Content | HitTest::TitleBarOrDragArea | HitTest::TabStrip => None,
+    }
+}
+
+/// Adjusts `rect`, which has been resized from `starting_rect` by dragging
+/// with `dragging_hit_test`, to keep the client aspect ratio. The size along
+/// `driving_dir` is kept, and the other dimension follows it. The edges
+/// opposite to the dragged ones are kept in place.
+pub(super) fn apply_aspect_ratio(
+    dragging_hit_test: HitTest,
+    starting_rect: RectI,
+    rect: RectI,
+    driving_dir: dim::Direction,
+    frame_size: dim::SizeI,
+    aspect_ratio: f32,
+) -> RectI {
+    let mut new_rect = rect;
+    match driving_dir {
+        dim::Direction::Horizontal => {
+            new_rect.h = follow_aspect_ratio::<dim::Horizontal>(rect.w, frame_size, aspect_ratio);
+        }
+        dim::Direction::Vertical => {
+            new_rect.w = follow_aspect_ratio::<dim::Vertical>(rect.h, frame_size, aspect_ratio);
+        }
+    }
+    let starting_r = starting_rect.x + starting_rect.w;
+    let starting_b = starting_rect.y + starting_rect.h;
+    match dragging_hit_test {
+        HitTest::TopLeftCorner => {
+            new_rect.x = starting_r - new_rect.w;
+            new_rect.y = starting_b - new_rect.h;
+        }
+        HitTest::TopRightCorner => new_rect.y = starting_b - new_rect.h,
+        HitTest::BottomLeftCorner => new_rect.x = starting_r - new_rect.w,
+        _ => {}
+    }
+    new_rect
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::test::init_win;
+    use super::super::WindowingEvent;
+    use super::*;
+
+    #[test]
+    fn test_aspect_ratio_resizing() {
+        let mut state = WindowingState::new();
+        state.set_dimensions([800.0, 600.0], 1.0);
+        let win_id = init_win(&mut state, [100.0, 100.0]);
+        let frame_size = state.win_frame_size_int(win_id);
+        let client_size = |state: &WindowingState| {
+            let rect = state.win_normal_rect_int(win_id).unwrap();
+            [rect.w - frame_size.w, rect.h - frame_size.h]
+        };
+
+        state.set_win_aspect_ratio(win_id, Some(2.0));
+        assert_eq!(client_size(&state), [200, 100]);
+
+        // Dragging an edge adjusts the other dimension.
+        assert!(state.win_drag_start(win_id, HitTest::RightBorder));
+        assert!(state.win_drag_update([100.0, 0.0]));
+        state.win_drag_end(false);
+        assert_eq!(client_size(&state), [300, 150]);
+
+        // Dragging a corner follows the dimension that changed more, keeping
+        // the opposite corner in place.
+        let rect = state.win_normal_rect(win_id).unwrap();
+        assert!(state.win_drag_start(win_id, HitTest::TopLeftCorner));
+        assert!(state.win_drag_update([-10.0, -50.0]));
+        state.win_drag_end(false);
+        assert_eq!(client_size(&state), [400, 200]);
+        let new_rect = state.win_normal_rect(win_id).unwrap();
+        assert_eq!(new_rect.x + new_rect.w, rect.x + rect.w);
+        assert_eq!(new_rect.y + new_rect.h, rect.y + rect.h);
+
+        // The minimum size is kept in both dimensions.
+        assert!(state.win_drag_start(win_id, HitTest::BottomBorder));
+        assert!(state.win_drag_update([0.0, -1000.0]));
+        state.win_drag_end(false);
+        assert_eq!(client_size(&state), [150, 75]);
+        assert_eq!(state.win_normal_rect(win_id).unwrap().pos(), new_rect.pos());
+    }
+
+    #[test]
+    fn test_aspect_ratio_corner_snapping() {
+        let mut state = WindowingState::new();
+        state.set_dimensions([800.0, 600.0], 1.0);
+        let win_id = init_win(&mut state, [100.0, 100.0]);
+        let other = init_win(&mut state, [400.0, 100.0]);
+        state.set_win_aspect_ratio(win_id, Some(2.0));
+        let frame_size = state.win_frame_size_int(win_id);
+        state.take_events();
+
+        // The right edge snaps next to the left edge of the other window, and
+        // the height follows it even though it has changed more.
+        assert!(state.win_drag_start(win_id, HitTest::BottomRightCorner));
+        assert!(state.win_drag_update([85.0, 80.0]));
+        state.win_drag_end(false);
+        let rect = state.win_normal_rect_int(win_id).unwrap();
+        assert_eq!(rect.x + rect.w, 400 - 8);
+        assert_eq!((rect.w - frame_size.w), (rect.h - frame_size.h) * 2);
+        assert!(state.take_events().contains(&WindowingEvent::Snapped {
+            win_id,
+            target: other
+        }));
+    }
+}
@@ -0,0 +1,149 @@
+use super::{WinId, WindowingEvent, WindowingState};
+
+impl WindowingState {
+    /// Requests the window to be closed, as when the close button of a window
+    /// with `WindowBuilder::auto_close` is clicked. This pushes a
+    /// `CloseRequested` event. Unless the request is vetoed with
+    /// `veto_win_close`, the window is closed by the first call to
+    /// `apply_close_requests` after the event has been taken with
+    /// `take_events`. As the `WindowingArea` applies the requests at the start
+    /// of each update and takes the events at the end of it, the application
+    /// can veto the request in the frame in which it gets the event.
+    pub fn request_win_close(&mut self, win_id: WinId) {
+        match self.win_state_mut(win_id) {
+            Some(win) if !win.is_closed && !win.is_close_requested => {
+                win.is_close_requested = true;
+                win.is_close_request_reported = false;
+            }
+            _ => return,
+        }
+        self.push_event(WindowingEvent::CloseRequested(win_id));
+    }
+
+    pub fn win_is_close_requested(&self, win_id: WinId) -> bool {
+        self.win_state(win_id).is_some_and(|x| x.is_close_requested)
+    }
+
+    /// Vetoes the pending close request of the window, for example to ask the
+    /// user to save their changes first.
+    pub fn veto_win_close(&mut self, win_id: WinId) {
+        if let Some(win) = self.win_state_mut(win_id) {
+            win.is_close_requested = false;
+            win.is_close_request_reported = false;
+        }
+    }
+
+    /// Checks whether the window has been closed. Closed windows are not
+    /// displayed, and are destroyed once the application stops making them.
+    pub fn win_is_closed(&self, win_id: WinId) -> bool {
+        self.win_state(win_id).is_some_and(|x| x.is_closed)
+    }
+
+    /// Displays a closed window again.
+    pub fn reopen_win(&mut self, win_id: WinId) {
+        self.set_win_closed(win_id, false);
+    }
+
+    /// Closes the windows whose close requests have been reported by
+    /// `take_events` and have not been vetoed.
+    pub fn apply_close_requests(&mut self) {
+        self.closed_wins.clear();
+        for i in 0..self.window_states.len() {
+            let win_id = match self.win_id_at(i) {
+                Some(x) => x,
+                None => continue,
+            };
+            match self.win_state(win_id) {
+                Some(win) if win.is_close_requested && win.is_close_request_reported => {}
+                _ => continue,
+            }
+            if self.current_dragging_win().map(|(id, _)| id) == Some(win_id) {
+                self.win_drag_end(true);
+            }
+            if self.keyboard_adjusting_win() == Some(win_id) {
+                self.keyboard_adjust_end(true);
+            }
+            self.set_win_closed(win_id, true);
+            self.closed_wins.push(win_id);
+            self.push_event(WindowingEvent::Closed(win_id));
+        }
+    }
+
+    /// Gets the windows closed by the last call to `apply_close_requests`,
+    /// i.e. the windows closed in the current update of the `WindowingArea`.
+    pub fn closed_wins(&self) -> &[WinId] {
+        &self.closed_wins
+    }
+
+    fn set_win_closed(&mut self, win_id: WinId, is_closed: bool) {
+        let win = match self.win_state_mut(win_id) {
+            Some(win) => win,
+            None => return,
+        };
+        win.is_close_requested = false;
+        win.is_close_request_reported = false;
+        if win.is_closed == is_closed {
+            return;
+        }
+        win.is_closed = is_closed;
+
+        if win.docked_side.is_some() {
+            self.update_work_area();
+        } else {
+            self.win_recompute_snapping_rect(win_id);
+            self.update_tile_layout();
+        }
+        self.update_owner_hidden();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::test::init_win;
+    use super::*;
+
+    #[test]
+    fn test_close_requests() {
+        let mut state = WindowingState::new();
+        state.set_dimensions([800.0, 600.0], 1.0);
+        let win_id = init_win(&mut state, [100.0, 100.0]);
+        state.take_events();
+
+        // The request is kept pending until its event has been taken, so that
+        // the application gets a chance to veto it.
+        state.request_win_close(win_id);
+        assert!(state.win_is_close_requested(win_id));
+        state.apply_close_requests();
+        assert!(!state.win_is_closed(win_id));
+        assert_eq!(
+            state.take_events(),
+            [WindowingEvent::CloseRequested(win_id)]
+        );
+
+        // A request vetoed in response to the event does not close the window.
+        state.veto_win_close(win_id);
+        state.apply_close_requests();
+        assert!(!state.win_is_closed(win_id));
+        assert!(!state.win_is_close_requested(win_id));
+        assert_eq!(state.closed_wins(), []);
+        assert!(state.win_display_rect(win_id).is_some());
+
+        state.request_win_close(win_id);
+        assert_eq!(
+            state.take_events(),
+            [WindowingEvent::CloseRequested(win_id)]
+        );
+        state.apply_close_requests();
+        assert!(state.win_is_closed(win_id));
+        assert_eq!(state.closed_wins(), [win_id]);
+        assert!(state.win_display_rect(win_id).is_none());
+        assert_eq!(state.take_events(), [WindowingEvent::Closed(win_id)]);
+
+        // The window is only reported as closed once.
+        state.apply_close_requests();
+        assert_eq!(state.closed_wins(), []);
+
+        state.reopen_win(win_id);
+        assert!(state.win_display_rect(win_id).is_some());
+    }
+}
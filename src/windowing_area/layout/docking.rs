@@ -0,0 +1,343 @@
+use super::{DraggingState, HitTest, RectF, RectI, WinId, WindowingState};
+
+/// An edge of the windowing area that a window can be docked to.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DockSide {
+    Left,
+    Right,
+    Top,
+    Bottom,
+}
+
+impl DockSide {
+    /// Whether the window is docked to the left or right edge, in which case
+    /// the dock size is the width of the window.
+    fn is_horizontal(self) -> bool {
+        match self {
+            DockSide::Left | DockSide::Right => true,
+            DockSide::Top | DockSide::Bottom => false,
+        }
+    }
+
+    /// The inner border of a window docked to this side, which acts as the
+    /// splitter between the docked window and the work area.
+    fn splitter_hit_test(self) -> HitTest {
+        match self {
+            DockSide::Left => HitTest::RightBorder,
+            DockSide::Right => HitTest::LeftBorder,
+            DockSide::Top => HitTest::BottomBorder,
+            DockSide::Bottom => HitTest::TopBorder,
+        }
+    }
+
+    /// Maps the hit test result of a window docked to this side. Only the
+    /// splitter can be used to resize a docked window.
+    pub(super) fn map_hit_test(self, ht: HitTest) -> HitTest {
+        match (self, ht) {
+            (_, HitTest::Content) | (_, HitTest::TitleBarOrDragArea) | (_, HitTest::TabStrip) => ht,
+            (
+                DockSide::Left,
+                HitTest::RightBorder | HitTest::TopRightCorner | HitTest::BottomRightCorner,
+            )
+            | (
+                DockSide::Right,
+                HitTest::LeftBorder | HitTest::TopLeftCorner | HitTest::BottomLeftCorner,
+            )
+            | (
+                DockSide::Top,
+                HitTest::BottomBorder | HitTest::BottomLeftCorner | HitTest::BottomRightCorner,
+            )
+            | (
+                DockSide::Bottom,
+                HitTest::TopBorder | HitTest::TopLeftCorner | HitTest::TopRightCorner,
+            ) => self.splitter_hit_test(),
+            _ => HitTest::Content,
+        }
+    }
+}
+
+impl WindowingState {
+    /// Gets the side of the windowing area that the window is docked to, or
+    /// `None` if the window is floating.
+    pub fn win_docked_side(&self, win_id: WinId) -> Option<DockSide> {
+        self.win_state(win_id)?.docked_side
+    }
+
+    /// Gets the work area, which is the part of the windowing area not taken
+    /// by docked windows. Floating windows are kept inside and snap to the
+    /// edges of the work area, and maximized windows fill the work area.
+    pub fn work_area(&self) -> RectF {
+        self.work_area
+    }
+
+    /// Gets the work area in unscaled physical pixels.
+    pub(super) fn work_area_int(&self) -> RectI {
+        let rect = self.work_area;
+        let hidpi_factor = self.hidpi_factor as f32;
+        RectI {
+            x: (rect.x * hidpi_factor).round() as i32,
+            y: (rect.y * hidpi_factor).round() as i32,
+            w: (rect.w * hidpi_factor).round() as i32,
+            h: (rect.h * hidpi_factor).round() as i32,
+        }
+    }
+
+    /// Docks the window to a side of the windowing area, or makes it floating
+    /// again with `None`. A docked window keeps its normal rect, which is
+    /// restored when it is undocked.
+    pub(crate) fn set_win_docked(&mut self, win_id: WinId, side: Option<DockSide>) {
+        let prev_side = match self.win_state(win_id) {
+            Some(win) => win.docked_side,
+            None => return,
+        };
+        if prev_side == side {
+            return;
+        }
+        // Docked windows cannot be in tab groups.
+        self.detach_tab(win_id);
+        if self.current_dragging_win().map(|(id, _)| id) == Some(win_id) {
+            self.win_drag_end(true);
+        }
+        let win = self.win_state_mut(win_id).unwrap_or_else(|| unreachable!());
+        if let (Some(prev_side), Some(side)) = (prev_side, side) {
+            if prev_side.is_horizontal() != side.is_horizontal() {
+                win.dock_size = None;
+            }
+        }
+        win.docked_side = side;
+        self.docked_wins.retain(|&docked| docked != win_id);
+        if side.is_some() {
+            self.docked_wins.push(win_id);
+        }
+        self.update_work_area();
+    }
+
+    /// Applies the docked side set in the `WindowBuilder` only when it differs
+    /// from the one applied in the previous frames, so that a window undocked
+    /// by `set_win_normal_rect` stays floating until the builder changes.
+    pub(crate) fn set_win_docked_from_builder(&mut self, win_id: WinId, side: Option<DockSide>) {
+        let win = match self.win_state_mut(win_id) {
+            Some(win) => win,
+            None => return,
+        };
+        if win.builder_docked_side == Some(side) {
+            return;
+        }
+        win.builder_docked_side = Some(side);
+        self.set_win_docked(win_id, side);
+    }
+
+    /// Recomputes the docked rects and the work area, then updates the tiles
+    /// and the windows anchored to the edges of the work area.
+    pub(super) fn update_work_area(&mut self) {
+        self.recompute_dock_layout();
+        self.update_tile_layout();
+        self.recompute_snapped_win_rects();
+    }
+
+    pub(super) fn recompute_dock_layout(&mut self) {
+        let hidpi_factor = self.hidpi_factor as f32;
+        let round = |x: f32| (x * hidpi_factor).round() / hidpi_factor;
+
+        let mut work_area = RectF {
+            x: 0.0,
+            y: 0.0,
+            w: self.area_size[0],
+            h: self.area_size[1],
+        };
+        for &win_id in &self.docked_wins {
+            let (min_size, max_size) = self.win_size_limits(win_id);
+            let win = match self.window_states[win_id.index()].as_mut() {
+                Some(win) if win.is_displayed() => win,
+                _ => continue,
+            };
+            let side = win.docked_side.unwrap_or_else(|| unreachable!());
+            let mut rect = work_area;
+            if side.is_horizontal() {
+                let w = win
+                    .dock_size
+                    .unwrap_or(win.rect.w)
+                    .min(max_size.w)
+                    .max(min_size.w);
+                rect.w = round(w.min(work_area.w.max(0.0)));
+                if side == DockSide::Left {
+                    work_area.x += rect.w;
+                } else {
+                    rect.x = work_area.x + work_area.w - rect.w;
+                }
+                work_area.w -= rect.w;
+            } else {
+                let h = win
+                    .dock_size
+                    .unwrap_or(win.rect.h)
+                    .min(max_size.h)
+                    .max(min_size.h);
+                rect.h = round(h.min(work_area.h.max(0.0)));
+                if side == DockSide::Top {
+                    work_area.y += rect.h;
+                } else {
+                    rect.y = work_area.y + work_area.h - rect.h;
+                }
+                work_area.h -= rect.h;
+            }
+            win.docked_rect = rect;
+        }
+        self.work_area = work_area;
+    }
+
+    /// Sets the dock size of the window from a rect in physical pixels.
+    pub(super) fn set_win_dock_size_int(&mut self, win_id: WinId, side: DockSide, rect: RectI) {
+        let hidpi_factor = self.hidpi_factor as f32;
+        let win = match self.win_state_mut(win_id) {
+            Some(win) => win,
+            None => return,
+        };
+        let dock_size = if side.is_horizontal() { rect.w } else { rect.h };
+        win.dock_size = Some(dock_size as f32 / hidpi_factor);
+        self.update_work_area();
+
+        // Keep the dock size within the limits applied by the dock layout.
+        let win = self.win_state_mut(win_id).unwrap_or_else(|| unreachable!());
+        if win.is_displayed() {
+            let docked_rect = win.docked_rect;
+            win.dock_size = Some(if side.is_horizontal() {
+                docked_rect.w
+            } else {
+                docked_rect.h
+            });
+        }
+    }
+
+    /// Starts dragging the splitter of a docked window.
+    pub(super) fn dock_drag_start(
+        &mut self,
+        win_id: WinId,
+        side: DockSide,
+        dragging_hit_test: HitTest,
+    ) -> bool {
+        if dragging_hit_test != side.splitter_hit_test() {
+            // Docked windows can neither be moved nor resized from other
+            // borders.
+            return false;
+        }
+        let starting_rect = match self.win_display_rect_int(win_id) {
+            Some(x) => x,
+            None => return false,
+        };
+        self.maybe_dragging_window = Some(DraggingState {
+            win_id,
+            dragging_hit_test,
+            starting_rect,
+            snap_candidates_x: Vec::new(),
+            last_snapped_x: None,
+            snap_candidates_y: Vec::new(),
+            last_snapped_y: None,
+            drop_target: None,
+        });
+        true
+    }
+
+    pub(super) fn dock_drag_update(
+        &mut self,
+        win_id: WinId,
+        side: DockSide,
+        starting_rect: RectI,
+        offset: [f32; 2],
+    ) -> bool {
+        let hidpi_factor = self.hidpi_factor as f32;
+        let dx = (offset[0] * hidpi_factor).round() as i32;
+        let dy = (offset[1] * hidpi_factor).round() as i32;
+        let mut rect = starting_rect;
+        match side {
+            DockSide::Left => rect.w += dx,
+            DockSide::Right => rect.w -= dx,
+            DockSide::Top => rect.h += dy,
+            DockSide::Bottom => rect.h -= dy,
+        }
+        self.set_win_dock_size_int(win_id, side, rect);
+        true
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::test::init_win;
+    use super::*;
+
+    #[test]
+    fn test_docking_reserves_work_area() {
+        let mut state = WindowingState::new();
+        state.set_dimensions([800.0, 600.0], 1.0);
+        let left = init_win(&mut state, [300.0, 300.0]);
+        let bottom = init_win(&mut state, [300.0, 300.0]);
+        let floating = init_win(&mut state, [0.0, 0.0]);
+        state.set_win_docked(left, Some(DockSide::Left));
+        state.set_win_docked(bottom, Some(DockSide::Bottom));
+        assert_eq!(state.win_docked_side(left), Some(DockSide::Left));
+        assert_eq!(state.win_docked_side(floating), None);
+
+        assert_eq!(
+            state.win_display_rect(left),
+            Some(RectF {
+                x: 0.0,
+                y: 0.0,
+                w: 208.0,
+                h: 600.0,
+            })
+        );
+        assert_eq!(
+            state.win_display_rect(bottom),
+            Some(RectF {
+                x: 208.0,
+                y: 473.0,
+                w: 592.0,
+                h: 127.0,
+            })
+        );
+        assert_eq!(
+            state.work_area(),
+            RectF {
+                x: 208.0,
+                y: 0.0,
+                w: 592.0,
+                h: 473.0,
+            }
+        );
+
+        state.ensure_all_win_in_area();
+        // The floating window is kept partially inside the work area.
+        assert_eq!(state.win_normal_rect(floating).unwrap().x, 150.0);
+        state.set_win_maximized(floating, true);
+        assert_eq!(state.win_display_rect(floating), Some(state.work_area()));
+
+        // Only the splitter can be dragged.
+        assert!(!state.win_drag_start(left, HitTest::TitleBarOrDragArea));
+        assert!(!state.win_drag_start(left, HitTest::LeftBorder));
+        assert!(state.win_drag_start(left, HitTest::RightBorder));
+        assert!(state.win_drag_update([50.0, 10.0]));
+        state.win_drag_end(false);
+        assert_eq!(state.win_display_rect(left).unwrap().w, 258.0);
+        assert_eq!(state.work_area().x, 258.0);
+
+        state.set_win_docked(left, None);
+        assert_eq!(state.win_normal_rect(left).unwrap().x, 300.0);
+        assert_eq!(state.work_area().x, 0.0);
+    }
+
+    #[test]
+    fn test_docked_from_builder() {
+        let mut state = WindowingState::new();
+        state.set_dimensions([800.0, 600.0], 1.0);
+        let win_id = init_win(&mut state, [300.0, 300.0]);
+        state.set_win_docked_from_builder(win_id, Some(DockSide::Left));
+        assert_eq!(state.win_docked_side(win_id), Some(DockSide::Left));
+
+        // Moving the window undocks it until the builder changes.
+        state.set_win_position(win_id, [100.0, 100.0]);
+        state.set_win_docked_from_builder(win_id, Some(DockSide::Left));
+        assert_eq!(state.win_docked_side(win_id), None);
+        assert_eq!(state.win_display_rect(win_id).unwrap().x, 100.0);
+        state.set_win_docked_from_builder(win_id, Some(DockSide::Right));
+        assert_eq!(state.win_docked_side(win_id), Some(DockSide::Right));
+    }
+}
@@ -0,0 +1,153 @@
+use super::{HitTest, WinId, WindowingState};
+
+/// A change to the windows of a `WindowingState`, caused either by the user
+/// or by the application.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum WindowingEvent {
+    /// The displayed position of the window has changed.
+    Moved(WinId),
+    /// The displayed size of the window has changed.
+    Resized(WinId),
+    /// The window has been brought to the top of its layer.
+    Raised(WinId),
+    Collapsed(WinId),
+    Expanded(WinId),
+    /// The window has been maximized to fill the work area.
+    Maximized(WinId),
+    /// The window has been restored from being maximized.
+    Restored(WinId),
+    /// The dragged window has snapped to an edge of another window.
+    Snapped {
+        win_id: WinId,
+        target: WinId,
+    },
+    /// The window has been anchored to, or released from, the edges of the
+    /// work area, which it follows when the area is resized.
+    Anchored {
+        win_id: WinId,
+        is_anchored: bool,
+    },
+    DragStarted {
+        win_id: WinId,
+        hit_test: HitTest,
+    },
+    DragEnded(WinId),
+    /// The drag has been aborted and the window has been restored to where it
+    /// was before the drag.
+    DragAborted(WinId),
+    /// The window has been requested to close with `request_win_close`.
+    CloseRequested(WinId),
+    /// The window has been closed after its close request was not vetoed.
+    Closed(WinId),
+    /// The window has been destroyed because it was not made in the last
+    /// update.
+    Swept(WinId),
+}
+
+impl WindowingState {
+    pub(super) fn push_event(&mut self, event: WindowingEvent) {
+        self.events.push(event);
+    }
+
+    /// Takes the events which have happened since the last call. The `Moved`
+    /// and `Resized` events are generated here by comparing the displayed
+    /// rects of the windows with those at the last call, so they are reported
+    /// at most once per call and after the other events.
+    ///
+    /// The `WindowingArea` takes the events on each update and provides them
+    /// through `WindowingContext::events`. Taking a `CloseRequested` event
+    /// allows the next `apply_close_requests` to close the window.
+    pub fn take_events(&mut self) -> Vec<WindowingEvent> {
+        for i in 0..self.window_states.len() {
+            let win_id = match self.win_id_at(i) {
+                Some(x) => x,
+                None => continue,
+            };
+            let rect = self.win_display_rect_int(win_id);
+            let win = match self.window_states[i].as_mut() {
+                Some(x) => x,
+                None => continue,
+            };
+            let prev_rect = std::mem::replace(&mut win.reported_rect, rect);
+            if let (Some(prev_rect), Some(rect)) = (prev_rect, rect) {
+                if prev_rect.pos() != rect.pos() {
+                    self.events.push(WindowingEvent::Moved(win_id));
+                }
+                if prev_rect.size() != rect.size() {
+                    self.events.push(WindowingEvent::Resized(win_id));
+                }
+            }
+        }
+        let events = std::mem::take(&mut self.events);
+        for event in &events {
+            if let WindowingEvent::CloseRequested(win_id) = *event {
+                if let Some(win) = self.win_state_mut(win_id) {
+                    win.is_close_request_reported = win.is_close_requested;
+                }
+            }
+        }
+        events
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::test::init_win;
+    use super::*;
+
+    #[test]
+    fn test_events() {
+        let mut state = WindowingState::new();
+        state.set_dimensions([800.0, 600.0], 1.0);
+        let win_id = init_win(&mut state, [100.0, 100.0]);
+        let other = init_win(&mut state, [400.0, 300.0]);
+        assert_eq!(state.take_events(), []);
+        state.bring_to_top(win_id);
+        state.bring_to_top(win_id);
+        assert_eq!(state.take_events(), [WindowingEvent::Raised(win_id)]);
+
+        assert!(state.win_drag_start(win_id, HitTest::TitleBarOrDragArea));
+        assert!(state.win_drag_update([10.0, 0.0]));
+        // Restarting the same drag continues it without more events.
+        assert!(state.win_drag_start(win_id, HitTest::TitleBarOrDragArea));
+        assert!(state.win_drag_update([10.0, 0.0]));
+        state.win_drag_end(false);
+        assert_eq!(
+            state.take_events(),
+            [
+                WindowingEvent::DragStarted {
+                    win_id,
+                    hit_test: HitTest::TitleBarOrDragArea
+                },
+                WindowingEvent::DragEnded(win_id),
+                WindowingEvent::Moved(win_id),
+            ]
+        );
+
+        // Placing the window at the margin from the edges of the work area
+        // anchors it.
+        state.set_win_position(win_id, [8.0, 8.0]);
+        assert_eq!(
+            state.take_events(),
+            [
+                WindowingEvent::Anchored {
+                    win_id,
+                    is_anchored: true
+                },
+                WindowingEvent::Moved(win_id),
+            ]
+        );
+
+        state.set_win_collapsed(win_id, true);
+        state.set_all_needed(false);
+        state.set_needed(other, true);
+        state.sweep_unneeded();
+        assert_eq!(
+            state.take_events(),
+            [
+                WindowingEvent::Collapsed(win_id),
+                WindowingEvent::Swept(win_id)
+            ]
+        );
+    }
+}
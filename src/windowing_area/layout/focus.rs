@@ -0,0 +1,115 @@
+use super::{WinId, WindowingState};
+
+impl WindowingState {
+    /// Gets the window which has the focus, if any. Unlike `active_win`, the
+    /// focus is not affected by bringing windows to the top, and no window is
+    /// focused after the user clicks outside of all windows. A window loses
+    /// the focus when it is hidden, and does not get it back when it is shown
+    /// again.
+    pub fn focused_win(&self) -> Option<WinId> {
+        self.focused_win
+            .filter(|&win_id| self.win_state(win_id).is_some_and(|x| x.is_displayed()))
+    }
+
+    pub fn win_is_focused(&self, win_id: WinId) -> bool {
+        self.focused_win() == Some(win_id)
+    }
+
+    /// Focuses the window without changing the z-order, or removes the focus
+    /// from all windows if `win_id` is `None`.
+    pub fn set_focused_win(&mut self, win_id: Option<WinId>) {
+        if let Some(win_id) = win_id {
+            if self.win_state(win_id).is_none() {
+                return;
+            }
+        }
+        self.focused_win = win_id;
+    }
+
+    /// Focuses the window and brings it to the top, as when the user clicks
+    /// on the window.
+    pub fn focus_and_bring_to_top(&mut self, win_id: WinId) {
+        self.set_focused_win(Some(win_id));
+        self.bring_to_top(win_id);
+    }
+
+    /// Removes the focus if the focused window is no longer displayed.
+    pub(super) fn update_focus_displayed(&mut self) {
+        if self.focused_win.is_some() && self.focused_win() != self.focused_win {
+            self.focused_win = None;
+        }
+    }
+
+    /// Checks whether the window has gained or lost the focus since the last
+    /// call for the same window. Returns `(focus_gained, focus_lost)`.
+    pub(crate) fn win_take_focus_change(&mut self, win_id: WinId) -> (bool, bool) {
+        let is_focused = self.win_is_focused(win_id);
+        let win = match self.win_state_mut(win_id) {
+            Some(win) => win,
+            None => return (false, false),
+        };
+        let was_focused = win.was_focused;
+        win.was_focused = is_focused;
+        (is_focused && !was_focused, was_focused && !is_focused)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::test::init_win;
+    use super::*;
+
+    #[test]
+    fn test_focus_separate_from_z_order() {
+        let mut state = WindowingState::new();
+        state.set_dimensions([800.0, 600.0], 1.0);
+        let win1 = init_win(&mut state, [10.0, 10.0]);
+        let win2 = init_win(&mut state, [10.0, 10.0]);
+        assert_eq!(state.focused_win(), Some(win2));
+        assert_eq!(state.win_take_focus_change(win2), (true, false));
+        assert_eq!(state.win_take_focus_change(win2), (false, false));
+
+        // Raising without focusing.
+        state.bring_to_top(win1);
+        assert_eq!(state.topmost_win(), Some(win1));
+        assert_eq!(state.focused_win(), Some(win2));
+        assert_ne!(state.topmost_win(), state.focused_win());
+
+        // Focusing without raising.
+        state.bring_to_top(win2);
+        state.set_focused_win(Some(win1));
+        assert_eq!(state.topmost_win(), Some(win2));
+        assert_eq!(state.focused_win(), Some(win1));
+        assert_ne!(state.topmost_win(), state.focused_win());
+        assert_eq!(state.win_take_focus_change(win1), (true, false));
+        assert_eq!(state.win_take_focus_change(win2), (false, true));
+
+        // Hidden windows lose the focus, and do not get it back when they are
+        // shown again.
+        state.set_win_hidden(win1, true);
+        assert_eq!(state.focused_win(), None);
+        state.set_win_hidden(win1, false);
+        assert_eq!(state.focused_win(), None);
+        assert_eq!(state.win_take_focus_change(win1), (false, true));
+
+        // Closed windows lose the focus.
+        let win3 = init_win(&mut state, [10.0, 10.0]);
+        assert_eq!(state.focused_win(), Some(win3));
+        state.request_win_close(win3);
+        state.take_events();
+        state.apply_close_requests();
+        assert_eq!(state.focused_win(), None);
+
+        state.focus_and_bring_to_top(win2);
+        assert_eq!(state.topmost_win(), Some(win2));
+        assert_eq!(state.focused_win(), Some(win2));
+        state.set_focused_win(None);
+        assert_eq!(state.focused_win(), None);
+
+        // Swept windows lose the focus.
+        state.set_focused_win(Some(win2));
+        state.set_all_needed(false);
+        state.sweep_unneeded();
+        assert_eq!(state.focused_win, None);
+    }
+}
@@ -0,0 +1,136 @@
+use super::{HitTest, RectI, WinId, WindowingState};
+
+/// The state of moving or resizing a window with the keyboard.
+pub(super) struct KeyboardAdjustState {
+    win_id: WinId,
+    /// The normal rect of the window when keyboard adjusting started.
+    starting_rect: RectI,
+    /// The accumulated offset of the current drag.
+    offset: [f32; 2],
+}
+
+impl WindowingState {
+    /// Gets the window being moved or resized with the keyboard, if any.
+    pub fn keyboard_adjusting_win(&self) -> Option<WinId> {
+        self.keyboard_adjusting
+            .as_ref()
+            .map(|x| x.win_id)
+            .filter(|&win_id| self.win_state(win_id).is_some())
+    }
+
+    /// Starts moving or resizing the window with the keyboard, ending the
+    /// keyboard adjusting of any other window. Returns whether the window can
+    /// be adjusted.
+    pub fn win_keyboard_adjust_start(&mut self, win_id: WinId) -> bool {
+        self.keyboard_adjust_end(false);
+        if self.win_display_rect_int(win_id).is_none() {
+            return false;
+        }
+        let starting_rect = match self.win_normal_rect_int(win_id) {
+            Some(x) => x,
+            None => return false,
+        };
+        self.keyboard_adjusting = Some(KeyboardAdjustState {
+            win_id,
+            starting_rect,
+            offset: [0.0, 0.0],
+        });
+        true
+    }
+
+    /// Moves the window being adjusted with the keyboard by `delta`, or
+    /// resizes it by moving its bottom-right corner if `is_resize` is true.
+    /// The adjustment is applied as a drag with `win_drag_update`, so the
+    /// window snaps and anchors the same way as when dragged with the mouse.
+    /// Returns whether the window is being dragged.
+    pub fn keyboard_adjust_by(&mut self, is_resize: bool, delta: [f32; 2]) -> bool {
+        let win_id = match self.keyboard_adjusting_win() {
+            Some(x) => x,
+            None => return false,
+        };
+        let hit_test = if is_resize {
+            HitTest::BottomRightCorner
+        } else {
+            HitTest::TitleBarOrDragArea
+        };
+        let prev_offset = if self.current_dragging_win() == Some((win_id, hit_test)) {
+            self.keyboard_adjusting
+                .as_ref()
+                .map_or([0.0, 0.0], |x| x.offset)
+        } else {
+            // Switching between moving and resizing finishes the previous
+            // drag and starts a new one from the current rect.
+            if !self.win_drag_start(win_id, hit_test) {
+                return false;
+            }
+            [0.0, 0.0]
+        };
+        let offset = [prev_offset[0] + delta[0], prev_offset[1] + delta[1]];
+        if let Some(adjusting) = self.keyboard_adjusting.as_mut() {
+            adjusting.offset = offset;
+        }
+        self.win_drag_update(offset)
+    }
+
+    /// Ends moving or resizing the window with the keyboard. If `abort` is
+    /// true, the window is restored to the rect it had when keyboard
+    /// adjusting started. Docked and tiled windows can only have their last
+    /// drag aborted.
+    pub fn keyboard_adjust_end(&mut self, abort: bool) {
+        let KeyboardAdjustState {
+            win_id,
+            starting_rect,
+            ..
+        } = match self.keyboard_adjusting.take() {
+            Some(x) => x,
+            None => return,
+        };
+        if self.current_dragging_win().map(|(id, _)| id) == Some(win_id) {
+            self.win_drag_end(abort);
+        }
+        if abort
+            && self.win_state(win_id).is_some()
+            && self.win_docked_side(win_id).is_none()
+            && !self.win_is_tiled(win_id)
+        {
+            self.set_win_normal_rect_int_with_followers(win_id, starting_rect);
+            self.win_update_snap_anchors(win_id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::test::init_win;
+    use super::*;
+
+    #[test]
+    fn test_keyboard_adjust() {
+        let mut state = WindowingState::new();
+        state.set_dimensions([800.0, 600.0], 1.0);
+        let win_id = init_win(&mut state, [100.0, 100.0]);
+        let rect = state.win_normal_rect(win_id).unwrap();
+        assert!(!state.keyboard_adjust_by(false, [10.0, 0.0]));
+
+        assert!(state.win_keyboard_adjust_start(win_id));
+        assert_eq!(state.keyboard_adjusting_win(), Some(win_id));
+        assert!(state.keyboard_adjust_by(false, [10.0, 0.0]));
+        assert!(state.keyboard_adjust_by(false, [10.0, 1.0]));
+        assert!(state.keyboard_adjust_by(true, [5.0, 0.0]));
+        let new_rect = state.win_normal_rect(win_id).unwrap();
+        assert_eq!(new_rect.x, rect.x + 20.0);
+        assert_eq!(new_rect.y, rect.y + 1.0);
+        assert_eq!(new_rect.w, rect.w + 5.0);
+
+        // Aborting restores the rect from before the moves and resizes.
+        state.keyboard_adjust_end(true);
+        assert_eq!(state.keyboard_adjusting_win(), None);
+        assert_eq!(state.current_dragging_win(), None);
+        assert_eq!(state.win_normal_rect(win_id).unwrap(), rect);
+
+        assert!(state.win_keyboard_adjust_start(win_id));
+        assert!(state.keyboard_adjust_by(false, [0.0, 30.0]));
+        state.keyboard_adjust_end(false);
+        assert_eq!(state.win_normal_rect(win_id).unwrap().y, rect.y + 30.0);
+    }
+}
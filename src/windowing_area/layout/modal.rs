@@ -0,0 +1,139 @@
+use super::{WinId, WindowingState};
+
+use std::time::{Duration, Instant};
+
+/// How long the title bar of a modal window flashes for.
+const FLASH_DURATION: Duration = Duration::from_millis(600);
+/// How long each flash of the title bar lasts.
+const FLASH_INTERVAL: Duration = Duration::from_millis(100);
+
+impl WindowingState {
+    pub fn win_is_modal(&self, win_id: WinId) -> bool {
+        self.win_state(win_id).is_some_and(|x| x.is_modal)
+    }
+
+    /// Sets whether the window is modal. While a modal window is displayed,
+    /// it is kept above all other windows regardless of their layers, and
+    /// presses on the other windows are blocked by the `WindowingArea`.
+    pub fn set_win_modal(&mut self, win_id: WinId, is_modal: bool) {
+        let win = match self.win_state_mut(win_id) {
+            Some(win) => win,
+            None => return,
+        };
+        if win.is_modal == is_modal {
+            return;
+        }
+        win.is_modal = is_modal;
+        if is_modal {
+            // Any ongoing drag of another window is blocked by the modal.
+            if self.current_dragging_win().map(|(id, _)| id) != Some(win_id) {
+                self.win_drag_end(true);
+            }
+            self.focus_and_bring_to_top(win_id);
+        } else {
+            self.sort_by_layer();
+        }
+    }
+
+    /// Applies the modal state set in the `WindowBuilder` only when it differs
+    /// from the one applied in the previous frames, so that a runtime
+    /// `set_win_modal` call is kept until the builder changes.
+    pub(crate) fn set_win_modal_from_builder(&mut self, win_id: WinId, is_modal: bool) {
+        let win = match self.win_state_mut(win_id) {
+            Some(win) => win,
+            None => return,
+        };
+        if win.builder_is_modal == Some(is_modal) {
+            return;
+        }
+        win.builder_is_modal = Some(is_modal);
+        self.set_win_modal(win_id, is_modal);
+    }
+
+    /// Gets the topmost modal window that is displayed, if any.
+    pub fn modal_win(&self) -> Option<WinId> {
+        self.bottom_to_top_list
+            .iter()
+            .rev()
+            .copied()
+            .find(|&win_id| {
+                self.win_state(win_id)
+                    .is_some_and(|win| win.is_modal && win.is_displayed())
+            })
+    }
+
+    /// Flashes the title bar of the window to draw the attention of the user,
+    /// for example when the user clicks outside of a modal window.
+    pub fn flash_win(&mut self, win_id: WinId) {
+        if self.win_state(win_id).is_some() {
+            self.flashing_win = Some((win_id, Instant::now()));
+        }
+    }
+
+    /// Checks whether the title bar of the window is flashing. Returns whether
+    /// the title bar should currently be highlighted, or `None` if the window
+    /// is not flashing.
+    pub(crate) fn win_flash_highlight(&self, win_id: WinId) -> Option<bool> {
+        let (flashing_win_id, start) = self.flashing_win?;
+        if flashing_win_id != win_id {
+            return None;
+        }
+        let elapsed = start.elapsed();
+        if elapsed >= FLASH_DURATION {
+            return None;
+        }
+        Some((elapsed.as_millis() / FLASH_INTERVAL.as_millis()) % 2 == 1)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::test::init_win;
+    use super::super::WindowLayer;
+    use super::*;
+
+    #[test]
+    fn test_modal_stays_on_top() {
+        let mut state = WindowingState::new();
+        state.set_dimensions([800.0, 600.0], 1.0);
+        let palette = init_win(&mut state, [10.0, 10.0]);
+        let modal = init_win(&mut state, [10.0, 10.0]);
+        let win = init_win(&mut state, [10.0, 10.0]);
+        state.set_win_layer(palette, WindowLayer::Floating);
+        assert_eq!(state.modal_win(), None);
+
+        state.set_win_modal(modal, true);
+        assert_eq!(state.modal_win(), Some(modal));
+        assert_eq!(state.topmost_win(), Some(modal));
+        state.bring_to_top(win);
+        state.bring_to_top(palette);
+        assert_eq!(state.topmost_win(), Some(modal));
+
+        state.set_win_hidden(modal, true);
+        assert_eq!(state.modal_win(), None);
+        state.set_win_modal(modal, false);
+        assert_eq!(state.topmost_win(), Some(palette));
+    }
+
+    #[test]
+    fn test_modal_from_builder() {
+        let mut state = WindowingState::new();
+        state.set_dimensions([800.0, 600.0], 1.0);
+        let modal = init_win(&mut state, [10.0, 10.0]);
+        let win = init_win(&mut state, [10.0, 10.0]);
+        state.set_win_modal_from_builder(modal, true);
+        assert_eq!(state.focused_win(), Some(modal));
+
+        // A runtime change is kept while the builder stays the same, and the
+        // focus is not taken back.
+        state.set_win_modal(modal, false);
+        state.focus_and_bring_to_top(win);
+        state.set_win_modal_from_builder(modal, true);
+        assert!(!state.win_is_modal(modal));
+        assert_eq!(state.focused_win(), Some(win));
+
+        state.set_win_modal_from_builder(modal, false);
+        state.set_win_modal_from_builder(modal, true);
+        assert!(state.win_is_modal(modal));
+    }
+}
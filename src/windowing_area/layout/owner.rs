@@ -0,0 +1,231 @@
+use super::{RectI, WinId, WindowingState};
+
+impl WindowingState {
+    /// Gets the owner of the window as set by `set_win_owner`.
+    pub fn win_owner(&self, win_id: WinId) -> Option<WinId> {
+        self.win_state(win_id)?.owner
+    }
+
+    /// Sets the owner of the window. Owned windows are kept above their
+    /// owner, are hidden while their owner is hidden or collapsed, and are
+    /// destroyed together with their owner. Setting an owner which would make
+    /// the window own itself is ignored.
+    pub fn set_win_owner(&mut self, win_id: WinId, owner: Option<WinId>) {
+        if self.win_owner(win_id) == owner {
+            return;
+        }
+        if let Some(owner) = owner {
+            if self.win_state(owner).is_none() || self.win_is_owned_by(owner, win_id) {
+                return;
+            }
+        }
+        match self.win_state_mut(win_id) {
+            Some(win) => win.owner = owner,
+            None => return,
+        }
+        if let Some(owner) = owner {
+            if self.win_z_order(win_id) < self.win_z_order(owner) {
+                self.raise_with_owned(win_id);
+                self.sort_by_layer();
+            }
+        }
+        self.update_owner_hidden();
+    }
+
+    /// Checks whether the window follows its owner when the owner is moved.
+    pub fn win_follows_owner(&self, win_id: WinId) -> bool {
+        self.win_state(win_id).is_some_and(|x| x.follows_owner)
+    }
+
+    /// Sets whether the window is moved along when its owner is dragged.
+    pub fn set_win_follows_owner(&mut self, win_id: WinId, follows_owner: bool) {
+        if let Some(win) = self.win_state_mut(win_id) {
+            win.follows_owner = follows_owner;
+        }
+    }
+
+    /// Checks whether the window is hidden because its owner, or the owner of
+    /// its owner, is hidden or collapsed.
+    pub fn win_is_hidden_by_owner(&self, win_id: WinId) -> bool {
+        self.win_state(win_id).is_some_and(|x| x.is_owner_hidden)
+    }
+
+    /// Checks whether `win_id` is `owner_id` or is owned by it, directly or
+    /// indirectly.
+    fn win_is_owned_by(&self, win_id: WinId, owner_id: WinId) -> bool {
+        let mut maybe_win_id = Some(win_id);
+        while let Some(win_id) = maybe_win_id {
+            if win_id == owner_id {
+                return true;
+            }
+            maybe_win_id = self.win_owner(win_id);
+        }
+        false
+    }
+
+    /// Gets the windows directly owned by the window, from bottom to top.
+    fn owned_wins(&self, owner_id: WinId) -> Vec<WinId> {
+        self.bottom_to_top_list
+            .iter()
+            .copied()
+            .filter(|&win_id| self.win_owner(win_id) == Some(owner_id))
+            .collect()
+    }
+
+    /// Moves the window to the top of `bottom_to_top_list`, followed by the
+    /// windows it owns. The caller is responsible for sorting the list by
+    /// layer afterwards.
+    pub(super) fn raise_with_owned(&mut self, win_id: WinId) {
+        let z_order = self.window_z_orders[win_id.index()] as usize;
+        let subslice = &mut self.bottom_to_top_list[z_order..];
+        subslice.rotate_left(1);
+        for (i, win) in subslice.iter().enumerate() {
+            self.window_z_orders[win.index()] = (i + z_order) as u32;
+        }
+        for owned_win_id in self.owned_wins(win_id) {
+            self.raise_with_owned(owned_win_id);
+        }
+    }
+
+    /// Recomputes whether each window is hidden by its owner, then updates the
+    /// layout if any window has been shown or hidden.
+    pub(super) fn update_owner_hidden(&mut self) {
+        let mut has_changed = false;
+        for i in 0..self.window_states.len() {
+            let win_id = match self.win_id_at(i) {
+                Some(x) => x,
+                None => continue,
+            };
+            let mut is_owner_hidden = false;
+            let mut maybe_owner = self.win_owner(win_id);
+            while let Some(owner) = maybe_owner {
+                match self.win_state(owner) {
+                    Some(win) if win.is_displayed() && !win.is_collapsed => {
+                        maybe_owner = win.owner;
+                    }
+                    _ => {
+                        is_owner_hidden = true;
+                        break;
+                    }
+                }
+            }
+            if let Some(win) = self.window_states[i].as_mut() {
+                if win.is_owner_hidden != is_owner_hidden {
+                    win.is_owner_hidden = is_owner_hidden;
+                    has_changed = true;
+                }
+            }
+        }
+        if has_changed {
+            self.update_work_area();
+        }
+        self.update_focus_displayed();
+    }
+
+    /// Marks the windows whose owners are not needed as not needed, so that
+    /// they are removed together with their owners by `sweep_unneeded`.
+    pub(super) fn mark_owned_unneeded(&mut self) {
+        let mut has_changed = true;
+        while has_changed {
+            has_changed = false;
+            for i in 0..self.window_states.len() {
+                let is_owner_needed = match self.window_states[i].as_ref() {
+                    Some(win) if win.is_needed => match win.owner {
+                        Some(owner) => self.win_state(owner).is_some_and(|x| x.is_needed),
+                        None => continue,
+                    },
+                    _ => continue,
+                };
+                if !is_owner_needed {
+                    if let Some(win) = self.window_states[i].as_mut() {
+                        win.is_needed = false;
+                        has_changed = true;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Sets the normal rect of the window in physical pixels, moving the
+    /// owned windows which follow it by the same offset.
+    pub(super) fn set_win_normal_rect_int_with_followers(&mut self, win_id: WinId, rect: RectI) {
+        let prev_rect = match self.win_normal_rect_int(win_id) {
+            Some(x) => x,
+            None => return,
+        };
+        self.set_win_normal_rect_int(win_id, rect);
+        let dx = rect.x - prev_rect.x;
+        let dy = rect.y - prev_rect.y;
+        if dx == 0 && dy == 0 {
+            return;
+        }
+        for owned_win_id in self.owned_wins(win_id) {
+            if !self.win_follows_owner(owned_win_id) {
+                continue;
+            }
+            let owned_rect = match self.win_normal_rect_int(owned_win_id) {
+                Some(x) => x,
+                None => continue,
+            };
+            self.set_win_normal_rect_int_with_followers(
+                owned_win_id,
+                RectI {
+                    x: owned_rect.x + dx,
+                    y: owned_rect.y + dy,
+                    ..owned_rect
+                },
+            );
+            self.win_update_snap_anchors(owned_win_id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::test::init_win;
+    use super::super::HitTest;
+    use super::*;
+
+    #[test]
+    fn test_owned_windows() {
+        let mut state = WindowingState::new();
+        state.set_dimensions([800.0, 600.0], 1.0);
+        let owner = init_win(&mut state, [100.0, 100.0]);
+        let tool = init_win(&mut state, [400.0, 100.0]);
+        let other = init_win(&mut state, [200.0, 200.0]);
+        state.set_win_owner(tool, Some(owner));
+        state.set_win_follows_owner(tool, true);
+        // Cycles are not allowed.
+        state.set_win_owner(owner, Some(tool));
+        assert_eq!(state.win_owner(owner), None);
+
+        // The owned window is kept above its owner.
+        state.bring_to_top(owner);
+        assert_eq!(state.topmost_win(), Some(tool));
+        assert_eq!(state.active_win(), Some(owner));
+        assert!(state.win_z_order(owner) > state.win_z_order(other));
+
+        // The owned window follows its owner.
+        assert!(state.win_drag_start(owner, HitTest::TitleBarOrDragArea));
+        assert!(state.win_drag_update([50.0, 20.0]));
+        assert_eq!(state.win_normal_rect(tool).unwrap().x, 450.0);
+        state.win_drag_end(true);
+        assert_eq!(state.win_normal_rect(tool).unwrap().x, 400.0);
+
+        // The owned window is hidden along with its owner.
+        state.set_win_collapsed(owner, true);
+        assert!(state.win_is_hidden_by_owner(tool));
+        assert!(state.win_display_rect(tool).is_none());
+        state.set_win_collapsed(owner, false);
+        assert!(state.win_display_rect(tool).is_some());
+
+        // The owned window is removed together with its owner.
+        state.set_all_needed(false);
+        state.set_needed(tool, true);
+        state.set_needed(other, true);
+        state.sweep_unneeded();
+        assert!(!state.win_id_is_valid(owner));
+        assert!(!state.win_id_is_valid(tool));
+        assert!(state.win_id_is_valid(other));
+    }
+}
@@ -30,6 +30,7 @@ impl<D: Dir> SnapSegment<D> {
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Anchor {
     None,
     LowerEdge,
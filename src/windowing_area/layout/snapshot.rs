@@ -0,0 +1,352 @@
+use super::{
+    dim, snapping, RectF, RectI, WinId, WindowLayer, WindowState, WindowingState, DEFAULT_MIN_SIZE,
+};
+
+/// A snapshot of the arrangement of all windows in a `WindowingState`. It can
+/// be obtained with `WindowingState::layout_snapshot` and later re-applied with
+/// `WindowingState::restore_layout_snapshot`, for example to persist the
+/// window layout between sessions when the `serde` feature is enabled.
+#[derive(Clone, PartialEq, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LayoutSnapshot {
+    /// The windows in bottom-to-top order.
+    windows: Vec<WindowSnapshot>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct WindowSnapshot {
+    /// The index of the `WinId` of the window. This is only used to match
+    /// windows which have no key.
+    index: u32,
+    /// The key of the window if it was allocated with
+    /// `WindowingState::id_for_key`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    key: Option<String>,
+    /// The x, y, width and height of the window in its normal state.
+    rect: [f32; 4],
+    is_hidden: bool,
+    is_collapsed: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    is_maximized: bool,
+    /// The docked width or height, which can be changed by the user.
+    #[cfg_attr(feature = "serde", serde(default))]
+    dock_size: Option<f32>,
+    anchor_x: snapping::Anchor,
+    anchor_y: snapping::Anchor,
+}
+
+impl WindowingState {
+    /// Takes a snapshot of the layout of all initialized windows, including
+    /// their rects, z-order, collapsed, maximized and hidden states and edge
+    /// anchors.
+    pub fn layout_snapshot(&self) -> LayoutSnapshot {
+        let windows = self
+            .bottom_to_top_list
+            .iter()
+            .filter_map(|&win_id| {
+                let win = self.win_state(win_id)?;
+                let RectF { x, y, w, h } = win.rect;
+                Some(WindowSnapshot {
+                    index: win_id.index,
+                    key: self.window_keys[win_id.index()].clone(),
+                    rect: [x, y, w, h],
+                    is_hidden: win.is_hidden,
+                    is_collapsed: win.is_collapsed,
+                    is_maximized: win.is_maximized,
+                    dock_size: win.dock_size,
+                    anchor_x: win.anchor_x,
+                    anchor_y: win.anchor_y,
+                })
+            })
+            .collect();
+        LayoutSnapshot { windows }
+    }
+
+    /// Re-applies a snapshot previously obtained with `layout_snapshot`.
+    /// Keyed windows are matched by their keys, allocating new `WinId`s for
+    /// keys which have not been seen yet, and other windows are matched by
+    /// their `WinId`. Windows which have not been initialized yet will take
+    /// their initial states from the snapshot. The
+    /// restored windows are kept inside the windowing area in the same way as
+    /// windows moved by the user, so that a layout saved with a larger area
+    /// still fits on a smaller one, and their sizes are kept within their
+    /// size limits. Windows with non-finite rects in the snapshot are skipped.
+    /// The hidden, collapsed and maximized states are applied in the same way
+    /// as by their setters, so a docked window is not maximized and the
+    /// `Collapsed` and `Maximized` events are pushed for the changes.
+    ///
+    /// The docked sides, the tab groups and the tiling mode are not part of
+    /// the snapshot. Existing windows keep them, and windows initialized from
+    /// the snapshot are floating and not in any tab group.
+    pub fn restore_layout_snapshot(&mut self, snapshot: &LayoutSnapshot) {
+        // Any ongoing drag would refer to the old layout.
+        self.win_drag_end(true);
+        self.keyboard_adjust_end(true);
+
+        let mut restored_wins = Vec::with_capacity(snapshot.windows.len());
+        for win_snapshot in &snapshot.windows {
+            if !win_snapshot.rect.iter().all(|x| x.is_finite()) {
+                continue;
+            }
+            let dock_size = win_snapshot.dock_size.filter(|x| x.is_finite());
+            let win_id = match &win_snapshot.key {
+                Some(key) => self.id_for_key(key),
+                None => {
+                    let win_idx = win_snapshot.index as usize;
+                    match self.win_id_at(win_idx) {
+                        // Don't let an unkeyed window clobber a keyed one.
+                        Some(win_id) if self.window_keys[win_idx].is_none() => win_id,
+                        _ => continue,
+                    }
+                }
+            };
+            let slot = &mut self.window_states[win_id.index()];
+            let [x, y, w, h] = win_snapshot.rect;
+            let rect = RectF { x, y, w, h };
+            match slot {
+                Some(win) => {
+                    win.rect = rect;
+                    win.dock_size = dock_size;
+                    win.anchor_x = win_snapshot.anchor_x;
+                    win.anchor_y = win_snapshot.anchor_y;
+                }
+                None => {
+                    *slot = Some(WindowState {
+                        title: String::new(),
+                        rect,
+                        min_size: dim::SizeF::from(DEFAULT_MIN_SIZE),
+                        max_size: None,
+                        is_resizable: true,
+                        aspect_ratio: None,
+                        is_pinned: false,
+                        layer: WindowLayer::Normal,
+                        is_modal: false,
+                        builder_is_modal: None,
+                        owner: None,
+                        follows_owner: false,
+                        is_owner_hidden: false,
+                        was_focused: false,
+                        reported_rect: None,
+                        is_close_requested: false,
+                        is_close_request_reported: false,
+                        is_closed: false,
+                        is_hidden: false,
+                        is_collapsed: false,
+                        is_maximized: false,
+                        // Keep the window alive until the application gets a
+                        // chance to call `make_window` for it.
+                        is_needed: true,
+                        is_inactive_tab: false,
+                        docked_side: None,
+                        builder_docked_side: None,
+                        dock_size,
+                        docked_rect: rect,
+                        tiled_rect: None,
+                        anchor_x: win_snapshot.anchor_x,
+                        anchor_y: win_snapshot.anchor_y,
+                    });
+                }
+            }
+
+            // Keep the size within the limits of the window, in case the
+            // snapshot was saved with other limits or has been edited.
+            let hidpi_factor = self.hidpi_factor as f32;
+            let size = self.constrain_win_size_int(
+                win_id,
+                dim::SizeI {
+                    w: (w * hidpi_factor).round() as i32,
+                    h: (h * hidpi_factor).round() as i32,
+                },
+            );
+            self.set_win_normal_rect_int(
+                win_id,
+                RectI {
+                    x: (x * hidpi_factor).round() as i32,
+                    y: (y * hidpi_factor).round() as i32,
+                    w: size.w,
+                    h: size.h,
+                },
+            );
+            self.set_win_hidden(win_id, win_snapshot.is_hidden);
+            self.set_win_collapsed(win_id, win_snapshot.is_collapsed);
+            self.set_win_maximized(win_id, win_snapshot.is_maximized);
+            restored_wins.push(win_id);
+        }
+
+        // Windows not in the snapshot are kept below the restored windows of
+        // the same layer in their existing order.
+        let mut bottom_to_top_list: Vec<WinId> = self
+            .bottom_to_top_list
+            .iter()
+            .copied()
+            .filter(|win_id| !restored_wins.contains(win_id))
+            .collect();
+        bottom_to_top_list.extend(restored_wins);
+        for (i, win_id) in bottom_to_top_list.iter().enumerate() {
+            self.window_z_orders[win_id.index()] = i as u32;
+        }
+        self.bottom_to_top_list = bottom_to_top_list;
+        self.sort_by_layer();
+        self.active_win = None;
+        self.mru_list = self.bottom_to_top_list.iter().rev().copied().collect();
+
+        self.update_work_area();
+        self.update_owner_hidden();
+        self.ensure_all_win_in_area();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::test::init_win;
+    use super::super::{DockSide, WindowInitialState, WindowingEvent};
+    use super::*;
+
+    #[test]
+    fn test_snapshot_restore_rects_and_z_order() {
+        let mut state = WindowingState::new();
+        state.set_dimensions([800.0, 600.0], 1.0);
+        let win1 = init_win(&mut state, [10.0, 20.0]);
+        let win2 = init_win(&mut state, [100.0, 200.0]);
+        state.set_win_collapsed(win1, true);
+        let snapshot = state.layout_snapshot();
+        assert_eq!(state.topmost_win(), Some(win2));
+
+        state.bring_to_top(win1);
+        state.set_win_collapsed(win1, false);
+        state.set_win_normal_rect(
+            win2,
+            RectF {
+                x: 300.0,
+                y: 300.0,
+                w: 250.0,
+                h: 250.0,
+            },
+        );
+
+        let mut restored = WindowingState::new();
+        restored.set_dimensions([800.0, 600.0], 1.0);
+        let r_win1 = restored.next_id();
+        let r_win2 = restored.next_id();
+        restored.restore_layout_snapshot(&snapshot);
+        assert_eq!(restored.topmost_win(), Some(r_win2));
+        assert!(restored.win_is_collapsed(r_win1));
+        assert_eq!(
+            restored.win_normal_rect(r_win2).unwrap().pos(),
+            dim::Point { x: 100.0, y: 200.0 }
+        );
+
+        state.restore_layout_snapshot(&snapshot);
+        assert_eq!(state.topmost_win(), Some(win2));
+        assert!(state.win_is_collapsed(win1));
+        assert_eq!(state.layout_snapshot(), snapshot);
+    }
+
+    #[test]
+    fn test_snapshot_restore_clamps_to_smaller_area() {
+        let mut state = WindowingState::new();
+        state.set_dimensions([1920.0, 1080.0], 1.0);
+        let win_id = init_win(&mut state, [1600.0, 900.0]);
+        let snapshot = state.layout_snapshot();
+
+        let mut restored = WindowingState::new();
+        restored.set_dimensions([800.0, 600.0], 1.0);
+        let win_id2 = restored.next_id();
+        assert_eq!(win_id, win_id2);
+        restored.restore_layout_snapshot(&snapshot);
+        let rect = restored.win_normal_rect(win_id2).unwrap();
+        assert!(rect.x < 800.0);
+        assert!(rect.y < 600.0);
+    }
+
+    #[test]
+    fn test_snapshot_restore_rejects_non_finite_and_hides_owned() {
+        let mut state = WindowingState::new();
+        state.set_dimensions([800.0, 600.0], 1.0);
+        let owner = init_win(&mut state, [10.0, 20.0]);
+        let tool = init_win(&mut state, [300.0, 20.0]);
+        state.set_win_owner(tool, Some(owner));
+        state.set_win_hidden(owner, true);
+        let mut snapshot = state.layout_snapshot();
+
+        // The owned window is hidden with its owner after the restore.
+        state.set_win_hidden(owner, false);
+        state.restore_layout_snapshot(&snapshot);
+        assert!(state.win_is_hidden_by_owner(tool));
+        assert!(state.win_display_rect(tool).is_none());
+
+        let rect = state.win_normal_rect(tool);
+        snapshot.windows[1].rect[0] = f32::NAN;
+        snapshot.windows[1].dock_size = Some(f32::INFINITY);
+        state.restore_layout_snapshot(&snapshot);
+        assert_eq!(state.win_normal_rect(tool), rect);
+    }
+
+    #[test]
+    fn test_snapshot_restore_uses_limits_and_setters() {
+        let mut state = WindowingState::new();
+        state.set_dimensions([800.0, 600.0], 1.0);
+        let win_id = init_win(&mut state, [10.0, 20.0]);
+        let docked = init_win(&mut state, [300.0, 20.0]);
+        state.set_win_max_size(win_id, Some([300.0, 200.0]));
+        let (min_size, max_size) = state.win_size_limits(win_id);
+        let mut snapshot = state.layout_snapshot();
+        state.set_win_docked(docked, Some(DockSide::Left));
+        state.take_events();
+
+        // Sizes outside the limits of the window are clamped.
+        snapshot.windows[0].rect[2] = 0.0;
+        snapshot.windows[0].rect[3] = -50.0;
+        snapshot.windows[0].is_collapsed = true;
+        snapshot.windows[1].is_maximized = true;
+        state.restore_layout_snapshot(&snapshot);
+        let rect = state.win_normal_rect(win_id).unwrap();
+        assert_eq!((rect.w, rect.h), (min_size.w, min_size.h));
+        snapshot.windows[0].rect[2] = 5000.0;
+        snapshot.windows[0].rect[3] = 5000.0;
+        state.restore_layout_snapshot(&snapshot);
+        let rect = state.win_normal_rect(win_id).unwrap();
+        assert_eq!((rect.w, rect.h), (max_size.w, max_size.h));
+
+        // A docked window is not maximized, and the changes are reported.
+        assert!(!state.win_is_maximized(docked));
+        assert!(state.win_is_collapsed(win_id));
+        let events = state.take_events();
+        assert!(events.contains(&WindowingEvent::Collapsed(win_id)));
+        assert!(!events.contains(&WindowingEvent::Maximized(docked)));
+    }
+
+    #[test]
+    fn test_snapshot_restore_by_key() {
+        let mut state = WindowingState::new();
+        state.set_dimensions([800.0, 600.0], 1.0);
+        let _unkeyed = init_win(&mut state, [10.0, 10.0]);
+        let tools = state.id_for_key("tools");
+        let editor = state.id_for_key("editor");
+        for &(win_id, x) in &[(tools, 50.0), (editor, 300.0)] {
+            state.ensure_init(win_id, || WindowInitialState {
+                client_size: [200.0, 100.0],
+                position: Some([x, 40.0]),
+                min_size: None,
+                is_collapsed: false,
+            });
+        }
+        assert_eq!(state.win_key(tools), Some("tools"));
+        assert_eq!(state.win_id_by_key("editor"), Some(editor));
+        assert_eq!(state.id_for_key("editor"), editor);
+        let snapshot = state.layout_snapshot();
+
+        // Create the windows in a different order in the new session.
+        let mut restored = WindowingState::new();
+        restored.set_dimensions([800.0, 600.0], 1.0);
+        let r_editor = restored.id_for_key("editor");
+        restored.restore_layout_snapshot(&snapshot);
+        let r_tools = restored
+            .win_id_by_key("tools")
+            .expect("Key should have been allocated by the restore");
+        assert_eq!(restored.win_normal_rect(r_editor).unwrap().x, 300.0);
+        assert_eq!(restored.win_normal_rect(r_tools).unwrap().x, 50.0);
+        assert_eq!(restored.topmost_win(), Some(r_editor));
+    }
+}
@@ -0,0 +1,436 @@
+use super::{snapping, DraggingState, HitTest, WinId, WindowingState};
+
+impl WindowingState {
+    fn tab_group_index(&self, win_id: WinId) -> Option<usize> {
+        self.tab_groups
+            .iter()
+            .position(|tabs| tabs.contains(&win_id))
+    }
+
+    /// Gets the windows in the tab group of the window in tab order, or `None`
+    /// if the window is not in a tab group.
+    pub fn win_tab_group(&self, win_id: WinId) -> Option<&[WinId]> {
+        let group_idx = self.tab_group_index(win_id)?;
+        Some(&self.tab_groups[group_idx])
+    }
+
+    /// Iterates over the windows of all tab groups.
+    pub fn tab_groups(&self) -> impl Iterator<Item = &[WinId]> {
+        self.tab_groups.iter().map(|tabs| tabs.as_slice())
+    }
+
+    /// Gets the window that is displayed in place of the window, which is the
+    /// active tab of its tab group, or the window itself if it is not in a tab
+    /// group.
+    pub fn win_active_tab(&self, win_id: WinId) -> Option<WinId> {
+        self.win_state(win_id)?;
+        match self.win_tab_group(win_id) {
+            Some(tabs) => tabs
+                .iter()
+                .copied()
+                .find(|&tab| self.win_is_active_tab(tab)),
+            None => Some(win_id),
+        }
+    }
+
+    /// Checks whether the window is displayed in its own frame, i.e. it is
+    /// either the active tab of its tab group or not in a tab group at all.
+    pub fn win_is_active_tab(&self, win_id: WinId) -> bool {
+        self.win_state(win_id).is_some_and(|x| !x.is_inactive_tab)
+    }
+
+    /// Whether the frame of the window shows a tab strip.
+    pub(crate) fn win_has_tab_strip(&self, win_id: WinId) -> bool {
+        self.win_state(win_id)
+            .is_some_and(|x| !x.is_inactive_tab && !x.is_collapsed)
+            && self.tab_group_index(win_id).is_some()
+    }
+
+    /// Gets the tab under `pos` in the tab strip of the window.
+    pub fn win_tab_at(&self, win_id: WinId, pos: [f32; 2]) -> Option<WinId> {
+        if self.specific_win_hit_test(win_id, pos) != Some(HitTest::TabStrip) {
+            return None;
+        }
+        let tabs = self.win_tab_group(win_id)?;
+        let rect = self.win_display_rect(win_id)?;
+        let border_thickness = self.frame_metrics.border_thickness as f32;
+        let tab_width = self.frame_metrics.tab_width(
+            (rect.w - border_thickness * 2.0) as f64,
+            tabs.len(),
+            self.hidpi_factor,
+        ) as f32;
+        let tab_idx = ((pos[0] - rect.x - border_thickness) / tab_width).floor();
+        if tab_idx < 0.0 {
+            return None;
+        }
+        tabs.get(tab_idx as usize).copied()
+    }
+
+    /// Copies the layout states, which are shared by all tabs of a tab group,
+    /// from one window to another.
+    fn copy_tab_layout_states(&mut self, from_win_id: WinId, to_win_id: WinId) {
+        let from = match self.win_state(from_win_id) {
+            Some(x) => x,
+            None => return,
+        };
+        let (rect, is_collapsed, is_maximized, anchor_x, anchor_y) = (
+            from.rect,
+            from.is_collapsed,
+            from.is_maximized,
+            from.anchor_x,
+            from.anchor_y,
+        );
+        if let Some(to) = self.win_state_mut(to_win_id) {
+            to.rect = rect;
+            to.is_collapsed = is_collapsed;
+            to.is_maximized = is_maximized;
+            to.anchor_x = anchor_x;
+            to.anchor_y = anchor_y;
+        }
+    }
+
+    /// Makes the window the active tab of its tab group. The window takes over
+    /// the rect and the z-order of the previously active tab, unless it is in
+    /// another layer, in which case it is kept within its own layer.
+    pub fn set_active_tab(&mut self, win_id: WinId) {
+        let active_win_id = match self.win_tab_group(win_id) {
+            Some(_) => self.win_active_tab(win_id),
+            None => return,
+        };
+        let active_win_id = match active_win_id {
+            Some(x) if x != win_id => x,
+            _ => return,
+        };
+        if self.current_dragging_win().map(|(id, _)| id) == Some(active_win_id) {
+            self.win_drag_end(true);
+        }
+        self.copy_tab_layout_states(active_win_id, win_id);
+        self.replace_tile(active_win_id, win_id);
+        if let Some(win) = self.win_state_mut(active_win_id) {
+            win.is_inactive_tab = true;
+        }
+        if let Some(win) = self.win_state_mut(win_id) {
+            win.is_inactive_tab = false;
+        }
+        if self.focused_win == Some(active_win_id) {
+            self.focused_win = Some(win_id);
+        }
+
+        let active_z_order = self.window_z_orders[active_win_id.index()];
+        let z_order = self.window_z_orders[win_id.index()];
+        self.bottom_to_top_list
+            .swap(active_z_order as usize, z_order as usize);
+        self.window_z_orders[active_win_id.index()] = z_order;
+        self.window_z_orders[win_id.index()] = active_z_order;
+        self.sort_by_layer();
+        self.update_tile_layout();
+        self.update_owner_hidden();
+    }
+
+    /// Merges the window, together with all the other tabs in its tab group,
+    /// into the tab group of `target_win_id`. The window becomes the active
+    /// tab and takes over the rect of the target tab group. Returns whether
+    /// the windows have been merged.
+    pub fn merge_into_tab_group(&mut self, win_id: WinId, target_win_id: WinId) -> bool {
+        match (self.win_state(win_id), self.win_state(target_win_id)) {
+            (Some(win), Some(target)) => {
+                if win.docked_side.is_some() || target.docked_side.is_some() {
+                    // Docked windows cannot be in tab groups.
+                    return false;
+                }
+            }
+            _ => return false,
+        }
+        let src_group_idx = self.tab_group_index(win_id);
+        if win_id == target_win_id
+            || (src_group_idx.is_some() && src_group_idx == self.tab_group_index(target_win_id))
+        {
+            return false;
+        }
+        self.win_drag_end(true);
+
+        let src_active_win_id = self.win_active_tab(win_id).unwrap_or(win_id);
+        let src_tabs = match src_group_idx {
+            Some(group_idx) => self.tab_groups.remove(group_idx),
+            None => vec![win_id],
+        };
+        let target_group_idx = match self.tab_group_index(target_win_id) {
+            Some(group_idx) => group_idx,
+            None => {
+                self.tab_groups.push(vec![target_win_id]);
+                self.tab_groups.len() - 1
+            }
+        };
+        for &tab in &src_tabs {
+            if let Some(win) = self.win_state_mut(tab) {
+                win.is_inactive_tab = true;
+            }
+        }
+        self.tab_groups[target_group_idx].extend(src_tabs);
+        self.set_active_tab(src_active_win_id);
+        self.update_tile_layout();
+        true
+    }
+
+    /// Removes the window from its tab group. If the window is the active tab,
+    /// the next tab becomes active. The tab group is dissolved once it has
+    /// only one window left.
+    pub(super) fn remove_from_tab_group(&mut self, win_id: WinId) {
+        let group_idx = match self.tab_group_index(win_id) {
+            Some(x) => x,
+            None => return,
+        };
+        if self.win_is_active_tab(win_id) {
+            let tabs = &self.tab_groups[group_idx];
+            let tab_idx = tabs.iter().position(|&tab| tab == win_id).unwrap_or(0);
+            let next_active = if tab_idx + 1 < tabs.len() {
+                tabs[tab_idx + 1]
+            } else {
+                tabs[tab_idx - 1]
+            };
+            self.set_active_tab(next_active);
+        }
+        let tabs = &mut self.tab_groups[group_idx];
+        tabs.retain(|&tab| tab != win_id);
+        if tabs.len() < 2 {
+            for tab in self.tab_groups.remove(group_idx) {
+                if let Some(win) = self.win_state_mut(tab) {
+                    win.is_inactive_tab = false;
+                }
+            }
+        }
+    }
+
+    /// Detaches the window from its tab group into a free window placed on
+    /// top of the tab group. Returns whether the window was in a tab group.
+    pub fn detach_tab(&mut self, win_id: WinId) -> bool {
+        if self.win_state(win_id).is_none() || self.tab_group_index(win_id).is_none() {
+            return false;
+        }
+        self.win_drag_end(true);
+        if !self.win_is_active_tab(win_id) {
+            if let Some(active_win_id) = self.win_active_tab(win_id) {
+                self.copy_tab_layout_states(active_win_id, win_id);
+            }
+        }
+        self.remove_from_tab_group(win_id);
+        if let Some(win) = self.win_state_mut(win_id) {
+            win.is_inactive_tab = false;
+            win.is_collapsed = false;
+            win.is_maximized = false;
+            win.anchor_x = snapping::Anchor::None;
+            win.anchor_y = snapping::Anchor::None;
+        }
+        self.bring_to_top(win_id);
+        self.update_tile_layout();
+        true
+    }
+
+    /// Starts dragging a tab in the tab strip of its tab group.
+    pub(super) fn tab_drag_start(&mut self, win_id: WinId) -> bool {
+        let active_win_id = match self.win_tab_group(win_id) {
+            Some(_) => self.win_active_tab(win_id),
+            None => None,
+        };
+        let active_win_id = match active_win_id {
+            // Detaching a tab from a maximized tab group would make it jump
+            // away from the pointer.
+            Some(x) if !self.win_is_maximized(x) => x,
+            _ => return false,
+        };
+        let starting_rect = match self.win_normal_rect_int(active_win_id) {
+            Some(x) => x,
+            None => return false,
+        };
+        self.maybe_dragging_window = Some(DraggingState {
+            win_id,
+            dragging_hit_test: HitTest::TabStrip,
+            starting_rect,
+            snap_candidates_x: Vec::new(),
+            last_snapped_x: None,
+            snap_candidates_y: Vec::new(),
+            last_snapped_y: None,
+            drop_target: None,
+        });
+        true
+    }
+
+    /// Updates the dragging of a tab. Once the tab has been dragged far enough
+    /// it is detached from the tab group and dragged as a free window.
+    pub(super) fn tab_drag_update(&mut self, win_id: WinId, offset: [f32; 2]) -> bool {
+        let hidpi_factor = self.hidpi_factor as f32;
+        let dx = (offset[0] * hidpi_factor).round() as i32;
+        let dy = (offset[1] * hidpi_factor).round() as i32;
+        let detach_threshold = (8.0 * hidpi_factor).round() as i32;
+        if dx.abs() < detach_threshold && dy.abs() < detach_threshold {
+            return true;
+        }
+        // The tab drag ends here, and a drag of the detached window starts.
+        self.win_drag_end(false);
+        if !self.detach_tab(win_id) {
+            return false;
+        }
+        // Continue as if the title bar of the detached window was dragged.
+        self.win_drag_start(win_id, HitTest::TitleBarOrDragArea) && self.win_drag_update(offset)
+    }
+
+    /// Updates the pointer position of the current window drag to find the
+    /// tab group that the window will be merged into when dropped. Windows
+    /// are merged by dropping them onto the title bar or the tab strip of
+    /// another window.
+    pub fn win_drag_set_pointer_pos(&mut self, pos: [f32; 2]) {
+        let (win_id, dragging_hit_test) = match self.current_dragging_win() {
+            Some(x) => x,
+            None => return,
+        };
+        let drop_target = if dragging_hit_test == HitTest::TitleBarOrDragArea {
+            self.bottom_to_top_list
+                .iter()
+                .rev()
+                .copied()
+                .filter(|&target_win_id| {
+                    target_win_id != win_id && self.win_docked_side(target_win_id).is_none()
+                })
+                .find_map(|target_win_id| {
+                    match self.specific_win_hit_test(target_win_id, pos)? {
+                        HitTest::TitleBarOrDragArea | HitTest::TabStrip => {
+                            Some(Some(target_win_id))
+                        }
+                        // The pointer is over another part of a window.
+                        _ => Some(None),
+                    }
+                })
+                .flatten()
+        } else {
+            None
+        };
+        if let Some(dragging_state) = self.maybe_dragging_window.as_mut() {
+            dragging_state.drop_target = drop_target;
+        }
+    }
+
+    /// Gets the window whose tab group the dragged window will be merged into
+    /// if it is dropped now.
+    pub fn current_drop_target(&self) -> Option<WinId> {
+        self.maybe_dragging_window
+            .as_ref()
+            .and_then(|dragging| dragging.drop_target)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::test::init_win;
+    use super::super::{RectF, WindowLayer, WindowingEvent};
+    use super::*;
+
+    #[test]
+    fn test_merge_and_detach_tabs() {
+        let mut state = WindowingState::new();
+        state.set_dimensions([800.0, 600.0], 1.0);
+        let win1 = init_win(&mut state, [10.0, 10.0]);
+        let win2 = init_win(&mut state, [300.0, 200.0]);
+        let target_rect = state.win_display_rect(win1).unwrap();
+
+        assert!(state.merge_into_tab_group(win2, win1));
+        assert_eq!(state.win_tab_group(win1), Some(&[win1, win2][..]));
+        assert_eq!(state.win_active_tab(win1), Some(win2));
+        assert!(state.win_display_rect(win1).is_none());
+        assert_eq!(state.win_display_rect(win2), Some(target_rect));
+
+        state.set_active_tab(win1);
+        assert!(state.win_is_active_tab(win1));
+        assert!(!state.win_is_active_tab(win2));
+
+        assert!(state.detach_tab(win1));
+        assert_eq!(state.tab_groups().count(), 0);
+        assert!(state.win_is_active_tab(win2));
+        assert_eq!(state.win_display_rect(win1), Some(target_rect));
+        assert_eq!(state.win_display_rect(win2), Some(target_rect));
+        assert_eq!(state.topmost_win(), Some(win1));
+    }
+
+    #[test]
+    fn test_set_active_tab_keeps_layers() {
+        let mut state = WindowingState::new();
+        state.set_dimensions([800.0, 600.0], 1.0);
+        let win1 = init_win(&mut state, [10.0, 10.0]);
+        let win2 = init_win(&mut state, [300.0, 200.0]);
+        let palette = init_win(&mut state, [500.0, 10.0]);
+        assert!(state.merge_into_tab_group(win2, win1));
+        state.set_win_layer(palette, WindowLayer::Floating);
+        state.set_win_layer(win1, WindowLayer::Floating);
+
+        // The tab in the normal layer is not raised above the floating
+        // windows when it becomes active.
+        state.set_active_tab(win1);
+        state.set_active_tab(win2);
+        assert_eq!(state.topmost_win(), Some(palette));
+        assert!(state.win_z_order(win2) < state.win_z_order(win1));
+    }
+
+    #[test]
+    fn test_drag_title_bar_onto_another_window() {
+        let mut state = WindowingState::new();
+        state.set_dimensions([800.0, 600.0], 1.0);
+        let win1 = init_win(&mut state, [10.0, 10.0]);
+        let win2 = init_win(&mut state, [300.0, 200.0]);
+
+        assert!(state.win_drag_start(win2, HitTest::TitleBarOrDragArea));
+        assert!(state.win_drag_update([-250.0, -190.0]));
+        state.win_drag_set_pointer_pos([70.0, 20.0]);
+        assert_eq!(state.current_drop_target(), Some(win1));
+        state.win_drag_end(false);
+        assert_eq!(state.win_tab_group(win2), Some(&[win1, win2][..]));
+        assert_eq!(
+            state.win_normal_rect(win2).map(|r| (r.x, r.y)),
+            Some((10.0, 10.0))
+        );
+
+        // Dragging the tab out of the tab strip detaches it.
+        let strip_y = 10.0 + 4.0 + 18.0 + 1.0 + 9.0;
+        assert_eq!(state.win_tab_at(win2, [20.0, strip_y]), Some(win1));
+        state.take_events();
+        assert!(state.win_drag_start(win1, HitTest::TabStrip));
+        assert!(state.win_drag_update([2.0, 0.0]));
+        assert!(state.win_tab_group(win1).is_some());
+        assert!(state.win_drag_update([100.0, 150.0]));
+        state.win_drag_end(false);
+        assert!(state.win_tab_group(win1).is_none());
+        // The tab drag is ended before the window drag starts.
+        let drag_events: Vec<_> = state
+            .take_events()
+            .into_iter()
+            .filter(|event| {
+                matches!(
+                    event,
+                    WindowingEvent::DragStarted { .. } | WindowingEvent::DragEnded(_)
+                )
+            })
+            .collect();
+        assert_eq!(
+            drag_events,
+            [
+                WindowingEvent::DragStarted {
+                    win_id: win1,
+                    hit_test: HitTest::TabStrip
+                },
+                WindowingEvent::DragEnded(win1),
+                WindowingEvent::DragStarted {
+                    win_id: win1,
+                    hit_test: HitTest::TitleBarOrDragArea
+                },
+                WindowingEvent::DragEnded(win1),
+            ]
+        );
+        assert_eq!(
+            state.win_normal_rect(win1),
+            Some(RectF {
+                x: 110.0,
+                y: 160.0,
+                w: 208.0,
+                h: 127.0,
+            })
+        );
+    }
+}
@@ -0,0 +1,607 @@
+use super::{dim, DraggingState, HitTest, RectF, RectI, WinId, WindowingState};
+
+/// The direction in which a tile is split into two.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(super) enum SplitDirection {
+    /// The two tiles are placed side by side.
+    Horizontal,
+    /// The two tiles are placed one above the other.
+    Vertical,
+}
+
+/// A node of the binary split tree used by the tiling mode.
+pub(super) enum TileNode {
+    Leaf(WinId),
+    Split {
+        direction: SplitDirection,
+        /// The fraction of the space taken by the `first` node.
+        ratio: f32,
+        first: Box<TileNode>,
+        second: Box<TileNode>,
+    },
+}
+
+/// Splits `rect` into two, with the split position aligned to the physical
+/// pixel grid.
+fn split_rect(
+    rect: RectF,
+    direction: SplitDirection,
+    ratio: f32,
+    hidpi_factor: f32,
+) -> (RectF, RectF) {
+    let (mut first, mut second) = (rect, rect);
+    match direction {
+        SplitDirection::Horizontal => {
+            first.w = (rect.w * ratio * hidpi_factor).round() / hidpi_factor;
+            second.x = rect.x + first.w;
+            second.w = rect.w - first.w;
+        }
+        SplitDirection::Vertical => {
+            first.h = (rect.h * ratio * hidpi_factor).round() / hidpi_factor;
+            second.y = rect.y + first.h;
+            second.h = rect.h - first.h;
+        }
+    }
+    (first, second)
+}
+
+/// Gets the direction of the split whose border is dragged with
+/// `dragging_hit_test`, and whether the dragged tile is in the `first` child of
+/// the split.
+fn border_split_side(dragging_hit_test: HitTest) -> Option<(SplitDirection, bool)> {
+    match dragging_hit_test {
+        HitTest::LeftBorder => Some((SplitDirection::Horizontal, false)),
+        HitTest::RightBorder => Some((SplitDirection::Horizontal, true)),
+        HitTest::TopBorder => Some((SplitDirection::Vertical, false)),
+        HitTest::BottomBorder => Some((SplitDirection::Vertical, true)),
+        _ => None,
+    }
+}
+
+impl TileNode {
+    fn contains(&self, win_id: WinId) -> bool {
+        match self {
+            TileNode::Leaf(leaf) => *leaf == win_id,
+            TileNode::Split { first, second, .. } => {
+                first.contains(win_id) || second.contains(win_id)
+            }
+        }
+    }
+
+    /// Removes the leaves for which `f` returns `false`. A split with only
+    /// one remaining child is replaced by the child.
+    fn retain<F>(self, f: &mut F) -> Option<TileNode>
+    where
+        F: FnMut(WinId) -> bool,
+    {
+        match self {
+            TileNode::Leaf(win_id) => {
+                if f(win_id) {
+                    Some(self)
+                } else {
+                    None
+                }
+            }
+            TileNode::Split {
+                direction,
+                ratio,
+                first,
+                second,
+            } => match (first.retain(f), second.retain(f)) {
+                (Some(first), Some(second)) => Some(TileNode::Split {
+                    direction,
+                    ratio,
+                    first: Box::new(first),
+                    second: Box::new(second),
+                }),
+                (Some(node), None) | (None, Some(node)) => Some(node),
+                (None, None) => None,
+            },
+        }
+    }
+
+    fn layout<F>(&self, rect: RectF, hidpi_factor: f32, f: &mut F)
+    where
+        F: FnMut(WinId, RectF),
+    {
+        match self {
+            TileNode::Leaf(win_id) => f(*win_id, rect),
+            TileNode::Split {
+                direction,
+                ratio,
+                first,
+                second,
+            } => {
+                let (first_rect, second_rect) = split_rect(rect, *direction, *ratio, hidpi_factor);
+                first.layout(first_rect, hidpi_factor, f);
+                second.layout(second_rect, hidpi_factor, f);
+            }
+        }
+    }
+
+    /// Gets the minimum and maximum sizes of the node from the size limits of
+    /// the tiles given by `limits`.
+    fn size_limits<F>(&self, limits: &F) -> (dim::SizeF, dim::SizeF)
+    where
+        F: Fn(WinId) -> (dim::SizeF, dim::SizeF),
+    {
+        match self {
+            TileNode::Leaf(win_id) => limits(*win_id),
+            TileNode::Split {
+                direction,
+                first,
+                second,
+                ..
+            } => {
+                let (min1, max1) = first.size_limits(limits);
+                let (min2, max2) = second.size_limits(limits);
+                match direction {
+                    SplitDirection::Horizontal => (
+                        dim::SizeF {
+                            w: min1.w + min2.w,
+                            h: min1.h.max(min2.h),
+                        },
+                        dim::SizeF {
+                            w: max1.w + max2.w,
+                            h: max1.h.min(max2.h),
+                        },
+                    ),
+                    SplitDirection::Vertical => (
+                        dim::SizeF {
+                            w: min1.w.max(min2.w),
+                            h: min1.h + min2.h,
+                        },
+                        dim::SizeF {
+                            w: max1.w.min(max2.w),
+                            h: max1.h + max2.h,
+                        },
+                    ),
+                }
+            }
+        }
+    }
+
+    /// Adjusts the ratios of the splits so that the tiles are kept within the
+    /// size limits given by `limits`. When there is not enough space for the
+    /// minimum sizes of both children of a split, the second child keeps its
+    /// minimum size.
+    fn clamp_ratios<F>(&mut self, rect: RectF, hidpi_factor: f32, limits: &F)
+    where
+        F: Fn(WinId) -> (dim::SizeF, dim::SizeF),
+    {
+        if let TileNode::Split {
+            direction,
+            ratio,
+            first,
+            second,
+        } = self
+        {
+            let (min1, max1) = first.size_limits(limits);
+            let (min2, max2) = second.size_limits(limits);
+            let (len, min1, max1, min2, max2) = match direction {
+                SplitDirection::Horizontal => (rect.w, min1.w, max1.w, min2.w, max2.w),
+                SplitDirection::Vertical => (rect.h, min1.h, max1.h, min2.h, max2.h),
+            };
+            if len > 0.0 {
+                let first_len = (len * *ratio)
+                    .min(max1)
+                    .max(len - max2)
+                    .max(min1)
+                    .min(len - min2);
+                *ratio = (first_len / len).clamp(0.0, 1.0);
+            }
+            let (first_rect, second_rect) = split_rect(rect, *direction, *ratio, hidpi_factor);
+            first.clamp_ratios(first_rect, hidpi_factor, limits);
+            second.clamp_ratios(second_rect, hidpi_factor, limits);
+        }
+    }
+
+    /// Replaces the leaf of `win_id` using `f`.
+    fn replace_leaf<F>(&mut self, win_id: WinId, f: F)
+    where
+        F: FnOnce(TileNode) -> TileNode,
+    {
+        match self {
+            TileNode::Leaf(leaf) if *leaf == win_id => {
+                let node = std::mem::replace(self, TileNode::Leaf(win_id));
+                *self = f(node);
+            }
+            TileNode::Leaf(_) => {}
+            TileNode::Split { first, second, .. } => {
+                if first.contains(win_id) {
+                    first.replace_leaf(win_id, f);
+                } else {
+                    second.replace_leaf(win_id, f);
+                }
+            }
+        }
+    }
+
+    /// Finds the innermost split in `split_direction` along the path to the
+    /// leaf of `win_id`, where the leaf is in the `first` child if `in_first`
+    /// is true or the `second` child otherwise. Returns the split and its rect.
+    fn find_split_mut(
+        &mut self,
+        rect: RectF,
+        hidpi_factor: f32,
+        win_id: WinId,
+        split_direction: SplitDirection,
+        in_first: bool,
+    ) -> Option<(&mut TileNode, RectF)> {
+        let mut found = None;
+        let mut node: &TileNode = self;
+        let mut node_rect = rect;
+        let mut depth = 0;
+        while let TileNode::Split {
+            direction,
+            ratio,
+            first,
+            second,
+        } = node
+        {
+            let child_in_first = first.contains(win_id);
+            if *direction == split_direction && child_in_first == in_first {
+                found = Some((depth, node_rect));
+            }
+            let (first_rect, second_rect) = split_rect(node_rect, *direction, *ratio, hidpi_factor);
+            if child_in_first {
+                node = first;
+                node_rect = first_rect;
+            } else {
+                node = second;
+                node_rect = second_rect;
+            }
+            depth += 1;
+        }
+
+        let (found_depth, found_rect) = found?;
+        let mut node = self;
+        for _ in 0..found_depth {
+            node = match node {
+                TileNode::Split { first, second, .. } => {
+                    if first.contains(win_id) {
+                        first.as_mut()
+                    } else {
+                        second.as_mut()
+                    }
+                }
+                TileNode::Leaf(_) => unreachable!(),
+            };
+        }
+        Some((node, found_rect))
+    }
+}
+
+impl WindowingState {
+    /// Checks whether the tiling mode is enabled.
+    pub fn is_tiling(&self) -> bool {
+        self.is_tiling
+    }
+
+    /// Enables or disables the tiling mode. In tiling mode, the visible
+    /// floating windows are arranged in a binary split tree filling the work
+    /// area, and the borders between the tiles can be dragged to resize them.
+    /// Collapsed and maximized windows are not tiled. The normal rects of the
+    /// windows are not changed by the tiling mode, so disabling it restores
+    /// the previous floating arrangement.
+    pub fn set_tiling(&mut self, is_tiling: bool) {
+        if self.is_tiling == is_tiling {
+            return;
+        }
+        self.win_drag_end(true);
+        self.is_tiling = is_tiling;
+        self.update_tile_layout();
+    }
+
+    /// Checks whether the window is currently arranged as a tile.
+    pub fn win_is_tiled(&self, win_id: WinId) -> bool {
+        self.win_state(win_id)
+            .is_some_and(|x| x.tiled_rect.is_some())
+    }
+
+    /// Replaces the tile of a window with another window, for example when
+    /// switching the active tab of a tab group.
+    pub(super) fn replace_tile(&mut self, win_id: WinId, new_win_id: WinId) {
+        if let Some(root) = self.tile_tree.as_mut() {
+            root.replace_leaf(win_id, |_| TileNode::Leaf(new_win_id));
+        }
+    }
+
+    /// Adds the windows that need to be tiled to the split tree and removes
+    /// the ones that no longer need to be, then recomputes the tile rects.
+    pub(super) fn update_tile_layout(&mut self) {
+        for win in self.window_states.iter_mut().filter_map(|x| x.as_mut()) {
+            win.tiled_rect = None;
+        }
+        if !self.is_tiling {
+            return;
+        }
+        let hidpi_factor = self.hidpi_factor as f32;
+        let work_area = self.work_area;
+        let tiled_wins: Vec<WinId> = self
+            .bottom_to_top_list
+            .iter()
+            .copied()
+            .filter(|&win_id| {
+                self.win_state(win_id).is_some_and(|win| {
+                    win.is_displayed()
+                        && win.docked_side.is_none()
+                        && !win.is_collapsed
+                        && !win.is_maximized
+                })
+            })
+            .collect();
+
+        let mut seen_wins = Vec::with_capacity(tiled_wins.len());
+        let mut root = self.tile_tree.take().and_then(|root| {
+            root.retain(&mut |win_id| {
+                if tiled_wins.contains(&win_id) && !seen_wins.contains(&win_id) {
+                    seen_wins.push(win_id);
+                    true
+                } else {
+                    false
+                }
+            })
+        });
+        for &win_id in &tiled_wins {
+            if seen_wins.contains(&win_id) {
+                continue;
+            }
+            root = Some(match root {
+                None => TileNode::Leaf(win_id),
+                Some(mut root) => {
+                    // Split the largest tile along its longer side.
+                    let mut largest: Option<(WinId, RectF)> = None;
+                    root.layout(work_area, hidpi_factor, &mut |leaf, rect| {
+                        if largest.is_none_or(|(_, r)| rect.w * rect.h > r.w * r.h) {
+                            largest = Some((leaf, rect));
+                        }
+                    });
+                    let (leaf, rect) = largest.unwrap_or_else(|| unreachable!());
+                    let direction = if rect.w >= rect.h {
+                        SplitDirection::Horizontal
+                    } else {
+                        SplitDirection::Vertical
+                    };
+                    root.replace_leaf(leaf, |node| TileNode::Split {
+                        direction,
+                        ratio: 0.5,
+                        first: Box::new(node),
+                        second: Box::new(TileNode::Leaf(win_id)),
+                    });
+                    root
+                }
+            });
+        }
+
+        if let Some(root) = root.as_mut() {
+            root.clamp_ratios(work_area, hidpi_factor, &|win_id| {
+                self.tile_size_limits(win_id)
+            });
+            let window_states = &mut self.window_states;
+            root.layout(work_area, hidpi_factor, &mut |win_id, rect| {
+                if let Some(win) = window_states[win_id.index()].as_mut() {
+                    win.tiled_rect = Some(rect);
+                }
+            });
+        }
+        self.tile_tree = root;
+    }
+
+    /// Gets the minimum and maximum sizes of the tile of a window. The tile
+    /// is never smaller than a collapsed window.
+    fn tile_size_limits(&self, win_id: WinId) -> (dim::SizeF, dim::SizeF) {
+        let (min_size, max_size) = self.win_size_limits(win_id);
+        let collapsed_size = self.collapsed_win_size();
+        let min_size = dim::SizeF {
+            w: min_size.w.max(collapsed_size.w),
+            h: min_size.h.max(collapsed_size.h),
+        };
+        (min_size, max_size)
+    }
+
+    /// Maps the hit test result of a tiled window. Only the borders shared
+    /// with other tiles can be used for resizing.
+    pub(super) fn tiled_hit_test(&self, tiled_rect: RectF, ht: HitTest) -> HitTest {
+        let work_area = self.work_area;
+        let tolerance = 0.5 / self.hidpi_factor as f32;
+        let has_left = tiled_rect.x > work_area.x + tolerance;
+        let has_right = tiled_rect.x + tiled_rect.w < work_area.x + work_area.w - tolerance;
+        let has_top = tiled_rect.y > work_area.y + tolerance;
+        let has_bottom = tiled_rect.y + tiled_rect.h < work_area.y + work_area.h - tolerance;
+        match ht {
+            HitTest::Content | HitTest::TitleBarOrDragArea | HitTest::TabStrip => ht,
+            HitTest::LeftBorder | HitTest::TopLeftCorner | HitTest::BottomLeftCorner
+                if has_left =>
+            {
+                HitTest::LeftBorder
+            }
+            HitTest::RightBorder | HitTest::TopRightCorner | HitTest::BottomRightCorner
+                if has_right =>
+            {
+                HitTest::RightBorder
+            }
+            HitTest::TopBorder | HitTest::TopLeftCorner | HitTest::TopRightCorner if has_top => {
+                HitTest::TopBorder
+            }
+            HitTest::BottomBorder | HitTest::BottomLeftCorner | HitTest::BottomRightCorner
+                if has_bottom =>
+            {
+                HitTest::BottomBorder
+            }
+            _ => HitTest::Content,
+        }
+    }
+
+    /// Starts dragging the border between a tiled window and its neighbour.
+    pub(super) fn tile_drag_start(&mut self, win_id: WinId, dragging_hit_test: HitTest) -> bool {
+        // Tiled windows cannot be moved, and only the borders shared with
+        // other tiles can be dragged.
+        let (direction, in_first) = match border_split_side(dragging_hit_test) {
+            Some(x) => x,
+            None => return false,
+        };
+        let hidpi_factor = self.hidpi_factor as f32;
+        let work_area = self.work_area;
+        let has_split = self.tile_tree.as_mut().is_some_and(|root| {
+            root.find_split_mut(work_area, hidpi_factor, win_id, direction, in_first)
+                .is_some()
+        });
+        if !has_split {
+            return false;
+        }
+        let starting_rect = match self.win_display_rect_int(win_id) {
+            Some(x) => x,
+            None => return false,
+        };
+        self.maybe_dragging_window = Some(DraggingState {
+            win_id,
+            dragging_hit_test,
+            starting_rect,
+            snap_candidates_x: Vec::new(),
+            last_snapped_x: None,
+            snap_candidates_y: Vec::new(),
+            last_snapped_y: None,
+            drop_target: None,
+        });
+        true
+    }
+
+    /// Moves the split between tiles so that the dragged border follows the
+    /// pointer, as far as the size limits of the tiles allow.
+    pub(super) fn tile_drag_update(
+        &mut self,
+        win_id: WinId,
+        dragging_hit_test: HitTest,
+        starting_rect: RectI,
+        offset: [f32; 2],
+    ) -> bool {
+        let hidpi_factor = self.hidpi_factor as f32;
+        let dx = (offset[0] * hidpi_factor).round() as i32;
+        let dy = (offset[1] * hidpi_factor).round() as i32;
+        let border_pos = match dragging_hit_test {
+            HitTest::LeftBorder => starting_rect.x + dx,
+            HitTest::RightBorder => starting_rect.x + starting_rect.w + dx,
+            HitTest::TopBorder => starting_rect.y + dy,
+            HitTest::BottomBorder => starting_rect.y + starting_rect.h + dy,
+            _ => return false,
+        };
+        let (direction, in_first) = match border_split_side(dragging_hit_test) {
+            Some(x) => x,
+            None => return false,
+        };
+        let border_pos = border_pos as f32 / hidpi_factor;
+        let work_area = self.work_area;
+        let root = match self.tile_tree.as_mut() {
+            Some(x) => x,
+            None => return false,
+        };
+        let (split, split_rect) =
+            match root.find_split_mut(work_area, hidpi_factor, win_id, direction, in_first) {
+                Some(x) => x,
+                None => return false,
+            };
+        let (start, len) = match direction {
+            SplitDirection::Horizontal => (split_rect.x, split_rect.w),
+            SplitDirection::Vertical => (split_rect.y, split_rect.h),
+        };
+        match split {
+            TileNode::Split { ratio, .. } if len > 0.0 => {
+                *ratio = ((border_pos - start) / len).clamp(0.0, 1.0);
+            }
+            _ => {}
+        }
+        // The ratio is kept within the size limits by the layout.
+        self.update_tile_layout();
+        true
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::test::init_win;
+    use super::super::WindowingEvent;
+    use super::*;
+
+    #[test]
+    fn test_tiling_and_restoring_floating_rects() {
+        let mut state = WindowingState::new();
+        state.set_dimensions([800.0, 600.0], 1.0);
+        let win1 = init_win(&mut state, [10.0, 10.0]);
+        let win2 = init_win(&mut state, [300.0, 200.0]);
+        let floating_rect = state.win_display_rect(win1);
+
+        state.set_tiling(true);
+        assert!(state.win_is_tiled(win1));
+        let rect1 = state.win_display_rect(win1).unwrap();
+        let rect2 = state.win_display_rect(win2).unwrap();
+        assert_eq!((rect1.x, rect1.w, rect1.h), (0.0, 400.0, 600.0));
+        assert_eq!((rect2.x, rect2.w, rect2.h), (400.0, 400.0, 600.0));
+
+        // A new window splits the largest tile.
+        let win3 = init_win(&mut state, [50.0, 50.0]);
+        let rect3 = state.win_display_rect(win3).unwrap();
+        assert_eq!((rect3.y, rect3.h), (300.0, 300.0));
+
+        // Drag the border between the left and right tiles.
+        assert!(!state.win_drag_start(win1, HitTest::TitleBarOrDragArea));
+        assert!(!state.win_drag_start(win1, HitTest::LeftBorder));
+        assert!(state.win_drag_start(win1, HitTest::RightBorder));
+        assert!(state.win_drag_update([100.0, 0.0]));
+        state.win_drag_end(false);
+        assert_eq!(state.win_display_rect(win1).unwrap().w, 500.0);
+        assert_eq!(state.win_display_rect(win2).unwrap().x, 500.0);
+        assert_eq!(state.win_display_rect(win2).unwrap().w, 300.0);
+        assert_eq!(state.win_display_rect(win3).unwrap().w, 500.0);
+
+        // A maximized window gives up its tile until it is restored.
+        state.take_events();
+        state.set_win_maximized(win3, true);
+        assert!(!state.win_is_tiled(win3));
+        assert_eq!(state.win_display_rect(win1).unwrap().h, 600.0);
+        state.set_win_maximized(win3, false);
+        assert!(state.win_is_tiled(win3));
+        assert_eq!(
+            state.take_events()[..2],
+            [
+                WindowingEvent::Maximized(win3),
+                WindowingEvent::Restored(win3)
+            ]
+        );
+
+        state.set_tiling(false);
+        assert!(!state.win_is_tiled(win1));
+        assert_eq!(state.win_display_rect(win1), floating_rect);
+    }
+
+    #[test]
+    fn test_tiles_keep_size_limits() {
+        let mut state = WindowingState::new();
+        state.set_dimensions([800.0, 600.0], 1.0);
+        let win1 = init_win(&mut state, [10.0, 10.0]);
+        let win2 = init_win(&mut state, [300.0, 200.0]);
+        state.set_win_max_size(win1, Some([200.0, 100.0]));
+        let (_, max_size) = state.win_size_limits(win1);
+
+        // The split follows the maximum size of the first tile.
+        state.set_tiling(true);
+        assert_eq!(state.win_display_rect(win1).unwrap().w, max_size.w);
+        assert_eq!(state.win_display_rect(win2).unwrap().x, max_size.w);
+
+        // The border cannot be dragged past the limits of either tile.
+        assert!(state.win_drag_start(win1, HitTest::RightBorder));
+        assert!(state.win_drag_update([100.0, 0.0]));
+        state.win_drag_end(false);
+        assert_eq!(state.win_display_rect(win1).unwrap().w, max_size.w);
+
+        // Changing the limits updates the tiles.
+        state.set_win_max_size(win1, None);
+        state.set_win_min_size(win2, [650.0, 100.0]);
+        let (min_size, _) = state.win_size_limits(win2);
+        assert_eq!(state.win_display_rect(win2).unwrap().w, min_size.w);
+        assert!(state.win_drag_start(win1, HitTest::RightBorder));
+        assert!(state.win_drag_update([600.0, 0.0]));
+        state.win_drag_end(false);
+        assert_eq!(state.win_display_rect(win2).unwrap().w, min_size.w);
+    }
+}
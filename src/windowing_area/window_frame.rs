@@ -1,7 +1,12 @@
 use super::layout;
 use layout::FrameMetrics;
 
-use crate::{classic_button, classic_frame, empty_widget::EmptyWidget};
+use crate::{
+    color_scheme::ColorScheme,
+    empty_widget::EmptyWidget,
+    frame_theme::{ButtonType, FrameTheme},
+    title_button::TitleButton,
+};
 use conrod_core::{
     builder_methods, color,
     position::{self},
@@ -16,10 +21,18 @@ pub struct WindowFrame<'a> {
     pub common: widget::CommonBuilder,
     pub style: Style,
     pub title: &'a str,
+    /// The titles of the tabs in the tab strip. The tab strip is only shown
+    /// when there are tabs.
+    pub tab_titles: &'a [&'a str],
+    pub active_tab: usize,
     pub is_focused: bool,
     pub is_collapsed: bool,
     pub is_collapsible: bool,
+    pub is_maximized: bool,
+    pub is_maximizable: bool,
     pub is_closable: bool,
+    pub theme: &'a dyn FrameTheme,
+    pub colors: ColorScheme,
     pub(crate) frame_metrics: FrameMetrics,
     pub hidpi_factor: f64,
 }
@@ -28,6 +41,11 @@ pub struct State {
     ids: Ids,
 }
 
+/// The style of the window decorations. The colors set in the style of a
+/// window override the `ColorScheme` it is drawn with. Unless a scheme is
+/// set, the scheme is made from the conrod `Theme` by
+/// `ColorScheme::from_theme`, so the colors can also be set for all windows
+/// with a `widget_styling` entry for this style in the theme.
 #[derive(Copy, Clone, Debug, Default, PartialEq, WidgetStyle)]
 pub struct Style {
     /// The color of the window frame.
@@ -53,10 +71,11 @@ pub struct Style {
     // /// Padding for the top edge of the Canvas' kid area.
     // #[conrod(default = "theme.padding.y.end")]
     // pub pad_top: Option<Scalar>,
-    /// The color of the title bar. Defaults to the color of the Canvas.
+    /// The color of the title bar of the focused window.
     #[conrod(default = "theme.shape_color")]
     pub title_bar_color: Option<Color>,
-    /// The color of the title bar's text.
+    /// The color of the title bar's text, which is also used for the icons
+    /// of the title bar buttons.
     #[conrod(default = "theme.label_color")]
     pub title_bar_text_color: Option<Color>,
     /// The font size for the title bar's text.
@@ -76,6 +95,7 @@ pub struct Style {
 #[derive(Clone, Debug)]
 pub struct Event {
     pub collapse_clicked: widget::button::TimesClicked,
+    pub maximize_clicked: widget::button::TimesClicked,
     pub close_clicked: widget::button::TimesClicked,
 }
 
@@ -86,21 +106,36 @@ widget_ids! {
         title_text_clip,
         title_text,
         button_collapse,
+        button_maximize,
         button_close,
+        tabs[],
+        tab_text_clips[],
+        tab_texts[],
     }
 }
 
 impl<'a> WindowFrame<'a> {
-    pub(crate) fn new(frame_metrics: FrameMetrics, hidpi_factor: f64) -> Self {
+    pub(crate) fn new(
+        theme: &'a dyn FrameTheme,
+        colors: ColorScheme,
+        frame_metrics: FrameMetrics,
+        hidpi_factor: f64,
+    ) -> Self {
         Self {
             common: widget::CommonBuilder::default(),
             style: Style::default(),
             title: "",
+            tab_titles: &[],
+            active_tab: 0,
+            theme,
+            colors,
             frame_metrics,
             hidpi_factor,
             is_focused: true,
             is_collapsed: false,
             is_collapsible: true,
+            is_maximized: false,
+            is_maximizable: false,
             is_closable: false,
         }
     }
@@ -110,16 +145,23 @@ impl<'a> WindowFrame<'a> {
         pub is_focused { is_focused = bool }
         pub is_collapsed { is_collapsed = bool }
         pub is_collapsible { is_collapsible = bool }
+        pub is_maximized { is_maximized = bool }
+        pub is_maximizable { is_maximizable = bool }
         pub is_closable { is_closable = bool }
+        pub frame_color { style.frame_color = Some(Color) }
+        pub title_bar_color { style.title_bar_color = Some(Color) }
+        pub title_bar_text_color { style.title_bar_text_color = Some(Color) }
+        pub title_bar_font_size { style.title_bar_font_size = Some(FontSize) }
     }
 
-    pub fn frame_color(mut self, color: Color) -> Self {
-        self.style.frame_color = Some(color);
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.style = style;
         self
     }
 
-    pub fn title_bar_color(mut self, color: Color) -> Self {
-        self.style.title_bar_color = Some(color);
+    pub fn tabs(mut self, tab_titles: &'a [&'a str], active_tab: usize) -> Self {
+        self.tab_titles = tab_titles;
+        self.active_tab = active_tab;
         self
     }
 }
@@ -140,10 +182,16 @@ impl<'a> Widget for WindowFrame<'a> {
     }
 
     fn kid_area(&self, args: KidAreaArgs<Self>) -> widget::KidArea {
-        let rect = args
-            .rect
-            .pad(self.frame_metrics.border_thickness)
-            .pad_top(self.frame_metrics.title_bar_height + self.frame_metrics.gap_below_title_bar);
+        let tab_strip_height = if self.tab_titles.is_empty() {
+            0.0
+        } else {
+            self.frame_metrics.tab_strip_height
+        };
+        let rect = args.rect.pad(self.frame_metrics.border_thickness).pad_top(
+            self.frame_metrics.title_bar_height
+                + self.frame_metrics.gap_below_title_bar
+                + tab_strip_height,
+        );
         widget::KidArea {
             rect,
             pad: conrod_core::position::Padding::none(),
@@ -162,26 +210,25 @@ impl<'a> Widget for WindowFrame<'a> {
         let Self {
             style,
             title,
+            tab_titles,
+            active_tab,
             is_focused,
             is_collapsed,
             is_collapsible,
+            is_maximized,
+            is_maximizable,
             is_closable,
+            theme,
+            mut colors,
             frame_metrics,
             hidpi_factor,
             ..
         } = self;
         let style: Style = style;
+        colors.apply_style(&style);
 
-        // Draw a classic frame using triangles:
-        let base_color = style.frame_color(ui.theme());
-        let triangles = classic_frame::make_panel_frame(
-            rect.bottom_left(),
-            rect.top_right(),
-            // The frame border is 4 units, but the actual panel frame border
-            // is only 2 units.
-            frame_metrics.border_thickness / 2.0,
-            base_color,
-        );
+        // Draw the frame using triangles:
+        let triangles = theme.frame(rect, &colors, &frame_metrics, hidpi_factor);
         widget::Triangles::multi_color(triangles)
             .with_bounding_rect(rect)
             .middle_of(id)
@@ -196,20 +243,7 @@ impl<'a> Widget for WindowFrame<'a> {
         let title_bar_rect = conrod_core::Rect::from_corners([left, bottom], [right, top]);
 
         // Draw a title bar rect:
-        let (color_left, color_right);
-        if is_focused {
-            color_left = color::rgba(0.0, 0.0, 0.5, 1.0);
-            color_right = color::rgba(0.05, 0.5, 0.8, 1.0);
-        } else {
-            color_left = color::rgba(0.5, 0.5, 0.5, 1.0);
-            color_right = color::rgba(0.7, 0.7, 0.7, 1.0);
-        }
-        let triangles = classic_frame::make_title_bar_gradient(
-            title_bar_rect.bottom_left(),
-            title_bar_rect.top_right(),
-            color_left,
-            color_right,
-        );
+        let triangles = theme.title_bar(title_bar_rect, &colors, is_focused, hidpi_factor);
         widget::Triangles::multi_color(triangles)
             .with_bounding_rect(title_bar_rect)
             .graphics_for(id)
@@ -223,11 +257,11 @@ impl<'a> Widget for WindowFrame<'a> {
         // Collapse (minimize) button:
         let collapse_clicked = if is_collapsible {
             let button_type = if is_collapsed {
-                classic_button::ButtonType::Uncollapse
+                ButtonType::Uncollapse
             } else {
-                classic_button::ButtonType::Collapse
+                ButtonType::Collapse
             };
-            classic_button::ClassicButton::new(button_type, hidpi_factor)
+            TitleButton::new(button_type, theme, colors, hidpi_factor)
                 .mid_left_with_margin_on(
                     state.ids.title_bar_box,
                     frame_metrics.title_button_padding,
@@ -242,7 +276,7 @@ impl<'a> Widget for WindowFrame<'a> {
 
         // Close button:
         let close_clicked = if is_closable {
-            classic_button::ClassicButton::new(classic_button::ButtonType::Close, hidpi_factor)
+            TitleButton::new(ButtonType::Close, theme, colors, hidpi_factor)
                 .mid_right_with_margin_on(
                     state.ids.title_bar_box,
                     frame_metrics.title_button_padding,
@@ -255,11 +289,41 @@ impl<'a> Widget for WindowFrame<'a> {
             widget::button::TimesClicked(0)
         };
 
+        // Maximize button:
+        let maximize_clicked = if is_maximizable {
+            let button_type = if is_maximized {
+                ButtonType::Restore
+            } else {
+                ButtonType::Maximize
+            };
+            let button = TitleButton::new(button_type, theme, colors, hidpi_factor);
+            let button = if is_closable {
+                button.left_from(state.ids.button_close, frame_metrics.title_button_padding)
+            } else {
+                button.mid_right_with_margin_on(
+                    state.ids.title_bar_box,
+                    frame_metrics.title_button_padding,
+                )
+            };
+            button
+                .w_h(button_width, button_height)
+                .parent(id)
+                .place_on_kid_area(false)
+                .set(state.ids.button_maximize, ui)
+        } else {
+            widget::button::TimesClicked(0)
+        };
+
         // Set the clipping box for the title bar text:
         let left_padding =
             frame_metrics.title_text_padding + if is_collapsible { button_width } else { 0.0 };
-        let right_padding =
-            frame_metrics.title_text_padding + if is_closable { button_width } else { 0.0 };
+        let right_padding = frame_metrics.title_text_padding
+            + if is_closable { button_width } else { 0.0 }
+            + if is_maximizable {
+                button_width + frame_metrics.title_button_padding
+            } else {
+                0.0
+            };
         EmptyWidget::new()
             .x_position_relative_to(
                 state.ids.title_bar_box,
@@ -283,14 +347,67 @@ impl<'a> Widget for WindowFrame<'a> {
             .left_justify()
             .w_of(state.ids.title_text_clip)
             .middle_of(state.ids.title_text_clip)
-            .color(color::WHITE)
+            .color(theme.title_text_color(&colors, is_focused))
             .font_size(font_size)
             .graphics_for(state.ids.title_text_clip)
             .place_on_kid_area(false)
             .set(state.ids.title_text, &mut ui);
 
+        // Draw the tab strip:
+        if !tab_titles.is_empty() && !is_collapsed {
+            if state.ids.tabs.len() != tab_titles.len() {
+                let target_len = tab_titles.len();
+                state.update(|state| {
+                    let id_gen = &mut ui.widget_id_generator();
+                    state.ids.tabs.resize(target_len, id_gen);
+                    state.ids.tab_text_clips.resize(target_len, id_gen);
+                    state.ids.tab_texts.resize(target_len, id_gen);
+                });
+            }
+            let strip_top = bottom - frame_metrics.gap_below_title_bar;
+            let strip_bottom = strip_top - frame_metrics.tab_strip_height;
+            let tab_width = frame_metrics.tab_width(right - left, tab_titles.len(), hidpi_factor);
+            for (i, &tab_title) in tab_titles.iter().enumerate() {
+                let tab_left = left + tab_width * i as f64;
+                let tab_rect = conrod_core::Rect::from_corners(
+                    [tab_left, strip_bottom],
+                    [tab_left + tab_width, strip_top],
+                );
+                let triangles = theme.tab(
+                    tab_rect,
+                    &colors,
+                    i == active_tab,
+                    &frame_metrics,
+                    hidpi_factor,
+                );
+                widget::Triangles::multi_color(triangles)
+                    .with_bounding_rect(tab_rect)
+                    .graphics_for(id)
+                    .place_on_kid_area(false)
+                    .set(state.ids.tabs[i], ui);
+                widget::Rectangle::fill(tab_rect.pad(frame_metrics.title_text_padding).dim())
+                    .xy(tab_rect.xy())
+                    .color(color::TRANSPARENT)
+                    .graphics_for(id)
+                    .place_on_kid_area(false)
+                    .crop_kids()
+                    .set(state.ids.tab_text_clips[i], ui);
+                widget::Text::new(tab_title)
+                    .no_line_wrap()
+                    .left_justify()
+                    .w_of(state.ids.tab_text_clips[i])
+                    .middle_of(state.ids.tab_text_clips[i])
+                    .color(theme.tab_text_color(&colors, i == active_tab))
+                    .font_size(font_size)
+                    .graphics_for(id)
+                    .place_on_kid_area(false)
+                    .set(state.ids.tab_texts[i], ui);
+            }
+        }
+
         Event {
             collapse_clicked,
+            maximize_clicked,
             close_clicked,
         }
     }
@@ -0,0 +1,168 @@
+use super::layout::FrameMetrics;
+
+use crate::{color_scheme::ColorScheme, frame_theme::FrameTheme};
+use conrod_core::{
+    color, widget, widget_ids, Colorable, FontSize, Positionable, Sizeable, Widget, WidgetCommon,
+    WidgetStyle,
+};
+
+/// The overlay listing the window titles while switching windows with the
+/// keyboard.
+#[derive(WidgetCommon)]
+pub struct WindowSwitcher<'a> {
+    #[conrod(common_builder)]
+    pub common: widget::CommonBuilder,
+    pub style: Style,
+    pub titles: &'a [&'a str],
+    pub selected: usize,
+    pub theme: &'a dyn FrameTheme,
+    pub colors: ColorScheme,
+    pub(crate) frame_metrics: FrameMetrics,
+    pub hidpi_factor: f64,
+}
+
+pub struct State {
+    ids: Ids,
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, WidgetStyle)]
+pub struct Style {
+    /// The font size for the window titles.
+    #[conrod(default = "theme.font_size_small")]
+    pub font_size: Option<FontSize>,
+}
+
+widget_ids! {
+    struct Ids {
+        frame,
+        highlight,
+        title_text_clips[],
+        title_texts[],
+    }
+}
+
+impl<'a> WindowSwitcher<'a> {
+    pub(crate) fn new(
+        titles: &'a [&'a str],
+        selected: usize,
+        theme: &'a dyn FrameTheme,
+        colors: ColorScheme,
+        frame_metrics: FrameMetrics,
+        hidpi_factor: f64,
+    ) -> Self {
+        Self {
+            common: widget::CommonBuilder::default(),
+            style: Style::default(),
+            titles,
+            selected,
+            theme,
+            colors,
+            frame_metrics,
+            hidpi_factor,
+        }
+    }
+
+    /// Gets the dimensions of the overlay for the number of titles.
+    pub(crate) fn dim_for(frame_metrics: FrameMetrics, title_count: usize) -> [f64; 2] {
+        [
+            320.0,
+            frame_metrics.title_bar_height * title_count as f64
+                + frame_metrics.border_thickness * 2.0,
+        ]
+    }
+}
+
+impl<'a> Widget for WindowSwitcher<'a> {
+    type State = State;
+    type Style = Style;
+    type Event = ();
+
+    fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
+        State {
+            ids: Ids::new(id_gen),
+        }
+    }
+
+    fn style(&self) -> Self::Style {
+        self.style
+    }
+
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        let widget::UpdateArgs {
+            id,
+            state,
+            rect,
+            ui,
+            ..
+        } = args;
+        let state: &mut widget::State<State> = state;
+        let Self {
+            style,
+            titles,
+            selected,
+            theme,
+            colors,
+            frame_metrics,
+            hidpi_factor,
+            ..
+        } = self;
+
+        let triangles = theme.frame(rect, &colors, &frame_metrics, hidpi_factor);
+        widget::Triangles::multi_color(triangles)
+            .with_bounding_rect(rect)
+            .graphics_for(id)
+            .place_on_kid_area(false)
+            .set(state.ids.frame, ui);
+
+        if state.ids.title_texts.len() != titles.len() {
+            let target_len = titles.len();
+            state.update(|state| {
+                let id_gen = &mut ui.widget_id_generator();
+                state.ids.title_text_clips.resize(target_len, id_gen);
+                state.ids.title_texts.resize(target_len, id_gen);
+            });
+        }
+
+        let inner_rect = rect.pad(frame_metrics.border_thickness);
+        let row_height = frame_metrics.title_bar_height;
+        let font_size = style.font_size(&ui.theme);
+        for (i, &title) in titles.iter().enumerate() {
+            let row_top = inner_rect.top() - row_height * i as f64;
+            let row_rect = conrod_core::Rect::from_corners(
+                [inner_rect.left(), row_top - row_height],
+                [inner_rect.right(), row_top],
+            );
+            let is_selected = i == selected;
+            if is_selected {
+                // The selected row is highlighted like a focused title bar.
+                let triangles = theme.title_bar(row_rect, &colors, true, hidpi_factor);
+                widget::Triangles::multi_color(triangles)
+                    .with_bounding_rect(row_rect)
+                    .graphics_for(id)
+                    .place_on_kid_area(false)
+                    .set(state.ids.highlight, ui);
+            }
+            widget::Rectangle::fill(row_rect.pad(frame_metrics.title_text_padding).dim())
+                .xy(row_rect.xy())
+                .color(color::TRANSPARENT)
+                .graphics_for(id)
+                .place_on_kid_area(false)
+                .crop_kids()
+                .set(state.ids.title_text_clips[i], ui);
+            widget::Text::new(title)
+                .no_line_wrap()
+                .left_justify()
+                .w_of(state.ids.title_text_clips[i])
+                .middle_of(state.ids.title_text_clips[i])
+                .color(if is_selected {
+                    theme.title_text_color(&colors, true)
+                } else {
+                    theme.tab_text_color(&colors, true)
+                })
+                .font_size(font_size)
+                .graphics_for(id)
+                .place_on_kid_area(false)
+                .set(state.ids.title_texts[i], ui);
+        }
+    }
+}